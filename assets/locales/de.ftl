@@ -0,0 +1,210 @@
+app-title = Paper Age
+mode-encrypt = Verschlüsseln
+mode-decrypt = Entschlüsseln
+
+## Encrypt form
+
+secret-label = Geheimnis:
+secret-file-toggle = Datei
+batch-toggle = Stapel
+split-generate = Auf { $count } Blätter aufteilen
+split-sheet-title = { $title } ({ $part } von { $count })
+separate-files-toggle = Einzeln
+batch-summary = { $saved } von { $total } Blättern gespeichert
+batch-saved = { $label }: { $path }
+batch-failed = { $label }: { $error }
+batch-not-saved = Nicht gespeichert
+secret-open = Öffnen
+secret-no-files = Keine Dateien ausgewählt
+secret-files =
+    { $count ->
+        [one] 1 Datei
+       *[other] { $count } Dateien, zu einem { $format }-Archiv zusammengefasst
+    }
+secret-drop = Dateien hier ablegen
+secret-url-placeholder = Oder eine Datei von https://… laden
+secret-url-load = Laden
+secret-source-text = „PDF erzeugen“ verschlüsselt den eingetippten Text
+secret-source-files = „PDF erzeugen“ verschlüsselt die ausgewählten Dateien
+secret-source-text-unused = Es ist auch Text eingetippt. Er bleibt erhalten, wird aber nur bei ausgeschalteter „Datei“ verschlüsselt.
+secret-source-files-unused = Es sind auch Dateien ausgewählt. Sie bleiben erhalten, werden aber nur bei eingeschalteter „Datei“ verschlüsselt.
+secret-paste = Einfügen
+secret-undo = Rückgängig
+secret-redo = Wiederholen
+secret-counter = { $chars } Zeichen, { $bytes } Bytes
+secret-size = { $percent } % des QR-Codes auf { $page } mit Fehlerkorrektur { $ecc }: { $encrypted } von { $capacity } Bytes nach der Verschlüsselung, { $size } Bytes davor
+passphrase-label = Passphrase:
+passphrase-placeholder = Passphrase
+passphrase-confirm-placeholder = Passphrase bestätigen
+passphrase-generate = Erzeugen
+passphrase-copy = Kopieren
+passphrase-copied = In die Zwischenablage kopiert
+passphrase-load = Aus Datei laden
+passphrase-loaded = Aus { $name } geladen
+recipients-label = Empfänger:
+recipients-toggle = Öffentliche Schlüssel
+strength-very-weak = Sehr schwach
+strength-weak = Schwach
+strength-fair = Mittel
+strength-strong = Stark
+strength-very-strong = Sehr stark
+strength-hint = { $label }: eine längere Passphrase wäre besser
+generate-pdf = PDF erzeugen
+check-inputs = Prüfen
+ready-to-generate = Bereit zum Erzeugen
+required-hint = * Zum Erzeugen erforderlich
+clear = Geheimnisse löschen
+export-qr-png = QR als PNG exportieren
+save-age = Als .age speichern
+encrypt-clipboard = Zwischenablage verschlüsseln
+dismiss = Schließen
+cancel = Abbrechen
+generating = PDF wird erzeugt …
+close-unsaved = Das Geheimnis wurde noch nicht gespeichert. Trotzdem schließen und verwerfen?
+close-discard = Verwerfen und schließen
+preview-rendering = Vorschau wird erstellt...
+preview-save = Speichern
+preview-print = Drucken
+preview-regenerate = Neu erzeugen
+saved-to = Gespeichert unter { $path }
+toast-pdf-generated = PDF erstellt
+open-folder = Ordner öffnen
+another-copy = Weitere Kopie erstellen
+another-copy-hint = Weitere Kopie erstellen speichert dasselbe Blatt erneut, das Formular bleibt unverändert. Geheimnisse löschen entfernt Geheimnis und Passphrase aus dem Formular.
+fingerprint = Fingerabdruck des Chiffrats: { $fingerprint }
+about = Über
+idle-cleared = Geheimnisse wegen Inaktivität gelöscht
+idle-countdown = Geheimnisse werden in { $seconds } s gelöscht
+
+## Extra settings
+
+extra = Extras
+help = Hilfe
+help-fields-title = Felder
+help-fields = Der Titel steht oben auf dem Blatt. Die Notizbeschriftung steht über einer leeren Zeile, auf der du von Hand einen Hinweis notieren kannst, etwa wo die Passphrase aufbewahrt wird. Schreibe nie die Passphrase selbst auf das Blatt. QR-Beschriftung und Datum stehen in derselben Zeile.
+help-file-title = Dateimodus
+help-file = Ist „Datei“ eingeschaltet, wird das Geheimnis aus Dateien gelesen statt eingetippt. Mehrere Dateien werden vor dem Verschlüsseln in ein Tar- oder Zip-Archiv gepackt, je nach Auswahl unter „Extras“. Dateien werden zwischen zwei Starts nie gespeichert.
+help-recovery-title = Das Geheimnis wiederherstellen
+help-recovery = Scanne den QR-Code mit einem beliebigen Leser oder tippe den gedruckten Text ab und entschlüssele das Ergebnis mit age („age -d“) und der Passphrase. Der Tab „Entschlüsseln“ erledigt das für ein PDF. Probiere es einmal aus, bevor du das Blatt weglegst.
+help-passphrase-title = Passphrasen
+help-passphrase = Verwende mehrere zufällige Wörter, wie die erzeugten, statt eines kurzen Passworts. Bewahre die Passphrase getrennt vom Blatt auf und sorge dafür, dass beide auffindbar sind, wenn das Geheimnis wiederhergestellt werden muss.
+profile-label = Profil:
+profile-placeholder = Kein Profil ausgewählt
+profile-delete = Löschen
+profile-name-placeholder = Profilname
+profile-save = Aktuelles speichern als…
+history-placeholder = Zuletzt
+clear-history = Letzte Titel und Beschriftungen vergessen
+title-label = Titel:
+notes-label = Notizfeld:
+notes-placeholder = Notizfeld
+qr-caption-label = QR-Beschriftung:
+qr-caption-placeholder = z. B. Serverschlüssel - nicht weitergeben
+author-label = PDF-Autor:
+subject-label = PDF-Betreff:
+metadata-hint = Wird in den PDF-Eigenschaften gespeichert und ist für alle mit der Datei lesbar.
+font-label = Schrift für Titel und Notizen:
+font-default = Standard (nur Lateinisch und Kyrillisch)
+notes-remaining =
+    { $count ->
+        [one] 1 Zeichen übrig
+       *[other] { $count } Zeichen übrig
+    }
+warning-notes-too-long = Die Notizbeschriftung ist auf { $max } Zeichen begrenzt
+warning-caption-too-long = Die QR-Beschriftung ist auf { $max } Zeichen begrenzt
+warning-notes-line-too-long = Mit Beschriftung und Datum ist die Notizzeile länger als { $max } Zeichen, daher wird keine Schreiblinie gezeichnet
+warning-font-missing-glyph = Die Schrift enthält kein Zeichen für „{ $char }“, stattdessen wird die Standardschrift verwendet
+warning-font-invalid = { $name } ist keine Schriftdatei
+output-name-label = Dateiname:
+page-size-label = Seitengröße:
+ecc-level-label = Minimale QR-Fehlerkorrektur:
+grid-toggle = Layout-Raster (5-mm-Debug-Raster über die Seite legen)
+print-plaintext-toggle = Den armierten Geheimtext unter dem QR-Code als Text drucken
+date-stamp-toggle = Erstellungsdatum neben der Notizbeschriftung drucken
+armor-toggle = Age-Armierung im QR-Code behalten, damit Scans direkt mit age entschlüsselt werden können. Ohne sie passen größere Geheimnisse.
+binary-qr-toggle = Rohen Geheimtext in den QR-Code schreiben, damit ein Drittel mehr hineinpasst als mit Text
+binary-qr-warning = Handykameras und die meisten QR-Code-Apps können binäre Codes nicht lesen. Zum Wiederherstellen brauchst du einen Leser, der Binärdaten erhält, wie zbarimg mit -Sbinary, oder den Tab „Entschlüsseln“.
+instructions-toggle = Vor dem Blatt eine Seite mit einer Anleitung zur Wiederherstellung einfügen
+instructions-title = So stellst du diese Sicherung wieder her
+instructions-intro = Die nächste Seite enthält ein mit age verschlüsseltes Geheimnis. age ist ein freies Verschlüsselungsprogramm. Zum Lesen brauchst du diese Seite und ihre Passphrase. Was neben „{ $notes }“ steht, verrät, wo die Passphrase zu finden ist.
+instructions-scan = 1. Scanne den QR-Code mit einem beliebigen QR-Code-Leser, etwa einer Handykamera, und speichere den enthaltenen Text in einer Datei namens secret.age. Lässt sich der Code nicht scannen, tippe stattdessen den darunter gedruckten Text ab und behalte die Zeilenumbrüche bei.
+instructions-compact = Dem QR-Code fehlen die erste und die letzte Zeile des üblichen age-Formats. Setze eine Zeile „-----BEGIN AGE ENCRYPTED FILE-----“ vor den Text und eine Zeile „-----END AGE ENCRYPTED FILE-----“ dahinter und brich den Text in Zeilen zu je 64 Zeichen um.
+instructions-binary = Der QR-Code enthält die verschlüsselte Datei selbst statt Text, den die meisten Handykameras nicht anzeigen können. Nutze einen Leser, der Binärdaten unverändert speichert, etwa "zbarimg --raw --oneshot -Sbinary scan.png > secret.age". Für den unter dem Code gedruckten Text gelten stattdessen die Schritte oben.
+instructions-decrypt = 2. Installiere age von https://age-encryption.org und führe „age --decrypt --output secret.txt secret.age“ aus. Gib die Passphrase ein, wenn danach gefragt wird. Das Geheimnis steht dann in secret.txt.
+instructions-archive = Das Geheimnis besteht aus mehreren Dateien, die in ein { $format }-Archiv gepackt sind. Verwende stattdessen „--output secret.{ $format }“ und entpacke diese Datei mit einem beliebigen Archivprogramm.
+instructions-app = Alternativ öffnest du das PDF dieser Sicherung in PaperAge GUI, wechselst zu „Entschlüsseln“ und gibst die Passphrase ein.
+verify-toggle = PDF nach dem Erzeugen zurücklesen und entschlüsseln
+open-after-save-toggle = PDF nach dem Speichern öffnen
+tray-toggle = Ab dem nächsten Start ein Symbol im Infobereich zeigen, um die Zwischenablage schnell zu verschlüsseln
+tray-quick-encrypt = Zwischenablage schnell verschlüsseln
+clipboard-generate-toggle = Beim Verschlüsseln der Zwischenablage sofort das PDF erzeugen
+reuse-payload-toggle = Verschlüsselte Nutzlast bei unveränderten Eingaben wiederverwenden (zum Testen)
+reuse-payload-warning = Gleiche Eingaben ergeben denselben QR-Code und Fingerabdruck, wodurch erkennbar ist, dass zwei Blätter dasselbe Geheimnis enthalten. Die PDF-Dateien unterscheiden sich weiterhin in ihren Metadaten, da weder age noch der PDF-Generator feste Zufallswerte unterstützen.
+trim-secret-toggle = Leerraum um das eingegebene Geheimnis entfernen
+normalize-newlines-toggle = Windows-Zeilenenden (CRLF) in Textgeheimnissen und -dateien in LF umwandeln
+encoding-label = Textkodierung:
+archive-format-label = Archivformat für mehrere Dateien:
+passphrase-words-label = Wörter in erzeugten Passphrasen:
+heading-toggle = Überschrift anzeigen
+theme-label = Design:
+theme-system = System
+text-size-label = Textgröße: { $percent } %
+idle-timeout-label = Geheimnisse nach Inaktivität löschen (Minuten):
+language-label = Sprache:
+
+## Decrypt form
+
+decrypt-pdf-label = Erzeugtes PDF:
+decrypt-open = Öffnen
+decrypt-parts-hint = Für ein auf mehrere Blätter aufgeteiltes Geheimnis öffne alle zugehörigen PDFs auf einmal
+decrypt-button = Entschlüsseln
+decrypted-secret = Entschlüsseltes Geheimnis:
+decrypted-binary = { $bytes } Bytes Binärdaten entschlüsselt
+decrypted-archive =
+    { $count ->
+        [one] { $format }-Archiv mit 1 Datei entschlüsselt:
+       *[other] { $format }-Archiv mit { $count } Dateien entschlüsselt:
+    }
+decrypted-archive-file = { $name } ({ $bytes } Bytes)
+
+## Warnings
+
+warning-select-pdf = Wähle ein PDF aus
+warning-parts-missing = Fehlende Teile des aufgeteilten Geheimnisses: { $parts }. Öffne alle zugehörigen PDFs zusammen
+warning-parts-mixed = Diese PDFs sind Teile verschiedener Geheimnisse
+warning-several-pdfs = Öffne ein einzelnes PDF oder alle Teile eines aufgeteilten Geheimnisses
+warning-select-file = Wähle eine Datei aus
+warning-archive-failed = Dateien konnten nicht archiviert werden: { $error }
+warning-url-status = Laden des Geheimnisses fehlgeschlagen mit Status { $status }
+warning-url-timeout = Laden des Geheimnisses dauerte zu lange, prüfe die Adresse und die Verbindung
+warning-url-failed = Laden des Geheimnisses fehlgeschlagen: { $error }
+warning-file-too-large = { $name } ist größer als { $limit } Bytes und passt nicht einmal auf mehrere Blätter verteilt
+warning-secret-empty = Das Geheimnis ist leer
+warning-secret-too-large = Das Geheimnis ist zu groß für einen QR-Code
+warning-secret-too-large-for-page = Zu groß für { $page }; { $fitting } würde passen
+warning-secret-near-capacity = Das Geheimnis erreicht fast die Kapazität des QR-Codes
+warning-secret-binary = Das Geheimnis sieht nach Binärdaten aus, wechsle in den Dateimodus, damit es unverändert bleibt
+warning-encoding-unsupported = { $encoding } kann das Zeichen „{ $char }“ nicht darstellen, wähle eine andere Textkodierung
+warning-secret-encrypted = Das Geheimnis ist bereits mit age verschlüsselt
+warning-secret-encrypted-confirm = Das Geheimnis ist bereits mit age verschlüsselt, erneut erzeugen, um es doppelt zu verschlüsseln
+warning-passphrase-empty = Die Passphrase ist leer
+warning-passphrase-mismatch = Die Passphrasen stimmen nicht überein
+warning-passphrase-short = Diese Passphrase ist kurz und leicht zu erraten. Mach sie länger, am besten mit ein paar weiteren Wörtern. Erneut erzeugen, um sie trotzdem zu verwenden.
+warning-passphrase-uniform = Diese Passphrase verwendet wenige Arten von Zeichen. Sorge für Abwechslung mit Großbuchstaben, Ziffern, Sonderzeichen oder anderen Wörtern. Erneut erzeugen, um sie trotzdem zu verwenden.
+warning-passphrase-file-empty = Die Passphrasendatei ist leer
+warning-passphrase-file-lines = Die Passphrasendatei darf nur eine Zeile enthalten
+warning-passphrase-file-binary = Die Passphrasendatei ist keine Textdatei
+warning-recipients-empty = Gib mindestens einen Empfänger an
+warning-recipient-invalid = Ungültiger Empfänger { $key }: { $error }
+warning-no-qr-code = Kein PaperAge-QR-Code im PDF gefunden
+warning-decryption-failed = Entschlüsselung fehlgeschlagen: { $error }
+warning-verify-failed = Das erzeugte PDF ließ sich nicht zum selben Geheimnis zurücklesen und entschlüsseln. Nicht drucken, sondern erneut erzeugen.
+error-encryption = Das Geheimnis konnte nicht verschlüsselt werden. Prüfe die Passphrase und versuche es erneut.
+error-document-init = Das PDF-Dokument konnte nicht vorbereitet werden. Bitte erneut versuchen.
+error-data-too-long = Das Geheimnis ist zu groß für einen QR-Code. Kürze es und versuche es erneut.
+error-ecc-too-high = Das Geheimnis passt bei der gewählten Fehlerkorrektur nicht in einen QR-Code. Wähle eine niedrigere Stufe oder kürze es.
+warning-save-failed = Die Datei konnte nicht gespeichert werden. Prüfe, ob genug Speicherplatz frei ist und der Ordner beschreibbar ist.
+warning-print-failed = Das PDF konnte nicht an den Drucker gesendet werden. Speichere es und drucke es stattdessen aus einem PDF-Betrachter.
+warning-open-failed = Das PDF wurde gespeichert, konnte aber nicht in einem PDF-Betrachter geöffnet werden.
+error-pdf-creation = Das PDF-Layout konnte nicht erstellt werden. Bitte erneut versuchen.
+error-qr-image = Das QR-Code-Bild konnte nicht erstellt werden.
@@ -0,0 +1,210 @@
+app-title = Paper Age
+mode-encrypt = Encrypt
+mode-decrypt = Decrypt
+
+## Encrypt form
+
+secret-label = Secret:
+secret-file-toggle = File
+batch-toggle = Batch
+split-generate = Split across { $count } sheets
+split-sheet-title = { $title } ({ $part } of { $count })
+separate-files-toggle = Separate
+batch-summary = Saved { $saved } of { $total } sheets
+batch-saved = { $label }: { $path }
+batch-failed = { $label }: { $error }
+batch-not-saved = Not saved
+secret-open = Open
+secret-no-files = No files selected
+secret-files =
+    { $count ->
+        [one] 1 file
+       *[other] { $count } files, combined into a { $format } archive
+    }
+secret-drop = Drop files here
+secret-url-placeholder = Or load a file from https://…
+secret-url-load = Load
+secret-source-text = Generate PDF encrypts the typed text
+secret-source-files = Generate PDF encrypts the selected files
+secret-source-text-unused = There is typed text as well. It is kept, but only encrypted with File switched off.
+secret-source-files-unused = There are selected files as well. They are kept, but only encrypted with File switched on.
+secret-paste = Paste
+secret-undo = Undo
+secret-redo = Redo
+secret-counter = { $chars } characters, { $bytes } bytes
+secret-size = { $percent }% of the QR code on { $page } with { $ecc } error correction: { $encrypted } of { $capacity } bytes after encryption, { $size } bytes before
+passphrase-label = Passphrase:
+passphrase-placeholder = Passphrase
+passphrase-confirm-placeholder = Confirm Passphrase
+passphrase-generate = Generate
+passphrase-copy = Copy
+passphrase-copied = Copied to clipboard
+passphrase-load = Load from file
+passphrase-loaded = Loaded from { $name }
+recipients-label = Recipients:
+recipients-toggle = Public keys
+strength-very-weak = Very weak
+strength-weak = Weak
+strength-fair = Fair
+strength-strong = Strong
+strength-very-strong = Very strong
+strength-hint = { $label }: consider a longer passphrase
+generate-pdf = Generate PDF
+check-inputs = Check
+ready-to-generate = Ready to generate
+required-hint = * Required to generate
+clear = Wipe secrets
+export-qr-png = Export QR PNG
+save-age = Save .age
+encrypt-clipboard = Encrypt clipboard
+dismiss = Dismiss
+cancel = Cancel
+generating = Generating the PDF…
+close-unsaved = The secret has not been saved yet. Close anyway and discard it?
+close-discard = Discard and close
+preview-rendering = Rendering preview...
+preview-save = Save
+preview-print = Print
+preview-regenerate = Regenerate
+saved-to = Saved to { $path }
+toast-pdf-generated = PDF generated
+open-folder = Open folder
+another-copy = Make another copy
+another-copy-hint = Make another copy saves the same sheet again, the form is kept as it is. Wipe secrets removes the secret and the passphrase from the form.
+fingerprint = Ciphertext fingerprint: { $fingerprint }
+about = About
+idle-cleared = Secrets cleared due to inactivity
+idle-countdown = Secrets will be cleared in { $seconds }s
+
+## Extra settings
+
+extra = Extra
+help = Help
+help-fields-title = Fields
+help-fields = The title is printed at the top of the sheet. The notes label is printed above an empty line where you can write a hint by hand, such as where the passphrase is kept. Never write the passphrase itself on the sheet. The QR caption and the date share that line.
+help-file-title = File mode
+help-file = With "File" switched on, the secret is read from files instead of being typed. Several files are packed into one tar or zip archive before encrypting, as chosen under Extra. Files are never remembered between launches.
+help-recovery-title = Recovering the secret
+help-recovery = Scan the QR code with any reader, or type the printed text, and decrypt the result with age ("age -d") and the passphrase. The Decrypt tab does the same with a PDF. Try it once before you put the sheet away.
+help-passphrase-title = Passphrases
+help-passphrase = Use several random words, like the generated ones, rather than a short password. Keep the passphrase somewhere other than the sheet, and make sure whoever needs to recover the secret can find both.
+profile-label = Profile:
+profile-placeholder = No profile selected
+profile-delete = Delete
+profile-name-placeholder = Profile name
+profile-save = Save current as…
+history-placeholder = Recent
+clear-history = Forget recent titles and labels
+title-label = Title:
+notes-label = Notes Label:
+notes-placeholder = Notes Label
+qr-caption-label = QR Caption:
+qr-caption-placeholder = e.g. Server key - do not share
+author-label = PDF Author:
+subject-label = PDF Subject:
+metadata-hint = Stored in the PDF properties, readable by anyone with the file.
+font-label = Font for the title and notes:
+font-default = Default (Latin and Cyrillic only)
+notes-remaining =
+    { $count ->
+        [one] 1 character left
+       *[other] { $count } characters left
+    }
+warning-notes-too-long = The notes label is limited to { $max } characters
+warning-caption-too-long = The QR caption is limited to { $max } characters
+warning-notes-line-too-long = With the caption and date the notes line is longer than { $max } characters, so no line is drawn to write on
+warning-font-missing-glyph = The font has no glyph for “{ $char }”, the default font is used instead
+warning-font-invalid = { $name } is not a font file
+output-name-label = Output File Name:
+page-size-label = Page Size:
+ecc-level-label = Minimum QR error correction:
+grid-toggle = Layout grid (overlay a 5 mm debug grid on the page)
+print-plaintext-toggle = Print the armored ciphertext as text below the QR code
+date-stamp-toggle = Print the creation date next to the notes label
+armor-toggle = Keep the age armor in the QR code, so scans can be decrypted with age directly. Without it, larger secrets fit.
+binary-qr-toggle = Put the raw ciphertext into the QR code, so a third more fits than as text
+binary-qr-warning = Phone cameras and most QR code apps can't read binary codes. Recovering the secret needs a reader that keeps binary data, like zbarimg with -Sbinary, or the Decrypt tab.
+instructions-toggle = Add a page with recovery instructions before the sheet
+instructions-title = How to recover this backup
+instructions-intro = The next page holds a secret encrypted with age, a free encryption tool. To read it you need that page and its passphrase. Whatever is written next to "{ $notes }" tells you where to find the passphrase.
+instructions-scan = 1. Scan the QR code with any QR code reader, such as a phone camera, and save the text it contains to a file named secret.age. If the code can't be scanned, type the text printed below it instead, keeping the line breaks.
+instructions-compact = The QR code leaves out the first and last line of the usual age format. Put a line "-----BEGIN AGE ENCRYPTED FILE-----" before the text and a line "-----END AGE ENCRYPTED FILE-----" after it, and break the text into lines of 64 characters.
+instructions-binary = The QR code holds the encrypted file itself rather than text, which most phone cameras can't show. Use a reader that saves binary data unchanged, such as "zbarimg --raw --oneshot -Sbinary scan.png > secret.age". The text printed below the code needs the steps above instead.
+instructions-decrypt = 2. Install age from https://age-encryption.org and run "age --decrypt --output secret.txt secret.age". Enter the passphrase when asked. The secret is then in secret.txt.
+instructions-archive = The secret holds several files packed into a { $format } archive. Use "--output secret.{ $format }" instead and unpack that file with any archive tool.
+instructions-app = Alternatively, open the PDF of this backup in PaperAge GUI, switch to Decrypt and enter the passphrase.
+verify-toggle = Read the PDF back and decrypt it after generating
+open-after-save-toggle = Open the PDF after saving it
+tray-toggle = Show a tray icon to quickly encrypt the clipboard, from the next launch on
+tray-quick-encrypt = Quick encrypt clipboard
+clipboard-generate-toggle = Generate the PDF right away when encrypting the clipboard
+reuse-payload-toggle = Reuse the encrypted payload while the inputs are unchanged (testing)
+reuse-payload-warning = Identical inputs give the same QR code and fingerprint, which reveals that two sheets hold the same secret. The PDF files still differ in their metadata, as neither age nor the PDF writer support fixed randomness.
+trim-secret-toggle = Strip whitespace around the typed secret
+normalize-newlines-toggle = Convert Windows line endings (CRLF) to LF in text secrets and files
+encoding-label = Text encoding:
+archive-format-label = Archive format for several files:
+passphrase-words-label = Generated Passphrase Words:
+heading-toggle = Show heading
+theme-label = Theme:
+theme-system = System
+text-size-label = Text Size: { $percent }%
+idle-timeout-label = Clear Secrets After Inactivity (minutes):
+language-label = Language:
+
+## Decrypt form
+
+decrypt-pdf-label = Generated PDF:
+decrypt-open = Open
+decrypt-parts-hint = For a secret split across several sheets, open all of its PDFs at once
+decrypt-button = Decrypt
+decrypted-secret = Decrypted secret:
+decrypted-binary = Decrypted { $bytes } bytes of binary data
+decrypted-archive =
+    { $count ->
+        [one] Decrypted a { $format } archive with 1 file:
+       *[other] Decrypted a { $format } archive with { $count } files:
+    }
+decrypted-archive-file = { $name } ({ $bytes } bytes)
+
+## Warnings
+
+warning-select-pdf = Select a PDF
+warning-parts-missing = Missing parts of the split secret: { $parts }. Open all of its PDFs together
+warning-parts-mixed = These PDFs are parts of different secrets
+warning-several-pdfs = Open a single PDF, or all parts of a split secret
+warning-select-file = Select file
+warning-archive-failed = Failed to archive files: { $error }
+warning-url-status = Loading the secret failed with status { $status }
+warning-url-timeout = Loading the secret took too long, check the address and the connection
+warning-url-failed = Loading the secret failed: { $error }
+warning-file-too-large = { $name } is larger than { $limit } bytes and cannot fit, not even split across sheets
+warning-secret-empty = Secret is empty
+warning-secret-too-large = Secret is too large to fit in a QR code
+warning-secret-too-large-for-page = Too large for { $page }; { $fitting } would fit
+warning-secret-near-capacity = Secret is close to the QR code capacity
+warning-secret-binary = Secret looks like binary data, switch to file mode to keep it intact
+warning-encoding-unsupported = { $encoding } can't represent the character “{ $char }”, pick another text encoding
+warning-secret-encrypted = Secret is already encrypted with age
+warning-secret-encrypted-confirm = Secret is already encrypted with age, generate again to encrypt it twice
+warning-passphrase-empty = Passphrase is empty
+warning-passphrase-mismatch = Passphrases do not match
+warning-passphrase-short = This passphrase is short and easy to guess. Add length, a few more words work best. Generate again to use it anyway.
+warning-passphrase-uniform = This passphrase uses few kinds of characters. Add variety with capitals, digits, symbols or other words. Generate again to use it anyway.
+warning-passphrase-file-empty = The passphrase file is empty
+warning-passphrase-file-lines = The passphrase file must contain a single line
+warning-passphrase-file-binary = The passphrase file is not text
+warning-recipients-empty = Enter at least one recipient
+warning-recipient-invalid = Invalid recipient { $key }: { $error }
+warning-no-qr-code = No PaperAge QR code found in the PDF
+warning-decryption-failed = Decryption failed: { $error }
+warning-verify-failed = The generated PDF could not be read back and decrypted to the same secret. Do not print it; try generating again.
+error-encryption = Could not encrypt the secret. Check the passphrase and try again.
+error-document-init = Could not prepare the PDF document. Please retry.
+error-data-too-long = The secret is too large for a QR code. Shorten it and try again.
+error-ecc-too-high = The secret does not fit in a QR code at the selected error correction level. Choose a lower level or shorten it.
+warning-save-failed = The file could not be saved. Check that there is enough space and that the folder is writable.
+warning-print-failed = The PDF could not be sent to the printer. Save it and print it from a PDF viewer instead.
+warning-open-failed = The PDF was saved, but could not be opened in a PDF viewer.
+error-pdf-creation = Could not lay out the PDF. Please retry.
+error-qr-image = Could not create the QR code image.
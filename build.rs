@@ -0,0 +1,26 @@
+//! Expose the locked versions of the encryption crates to the about panel.
+
+use std::fs;
+
+const CRATES: [(&str, &str); 2] = [("paper-age", "PAPER_AGE_VERSION"), ("age", "AGE_VERSION")];
+
+fn main() {
+    println!("cargo:rerun-if-changed=Cargo.lock");
+    let lock = fs::read_to_string("Cargo.lock").unwrap_or_default();
+    for (name, variable) in CRATES {
+        let version = locked_version(&lock, name).unwrap_or("unknown");
+        println!("cargo:rustc-env={variable}={version}");
+    }
+}
+
+/// Version of the package `name` in the lock file, where every package
+/// starts with its `name` line directly followed by its `version` line
+fn locked_version<'a>(lock: &'a str, name: &str) -> Option<&'a str> {
+    let name_line = format!("name = \"{name}\"");
+    let mut lines = lock.lines();
+    lines.find(|line| *line == name_line)?;
+    lines
+        .next()?
+        .strip_prefix("version = \"")?
+        .strip_suffix('"')
+}
@@ -0,0 +1,208 @@
+//! Packing several secret files into one before encrypting.
+//!
+//! Both formats store the files uncompressed, as the ciphertext of random
+//! secrets wouldn't get any smaller. Zip needs less room per file than tar,
+//! whose entries are padded to 512 byte blocks.
+
+use age::secrecy::zeroize::Zeroizing;
+use arcstr::ArcStr;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{self, Write};
+use zip::write::SimpleFileOptions;
+
+use crate::SecretBytes;
+
+/// Size of a tar block, headers take one and contents are padded to whole ones
+const TAR_BLOCK: usize = 512;
+
+/// Signature at the start of a zip archive
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+
+/// Sizes of the zip records written for every file, without the file name
+const ZIP_LOCAL_HEADER_SIZE: usize = 30;
+const ZIP_CENTRAL_HEADER_SIZE: usize = 46;
+
+/// Size of the record ending a zip archive
+const ZIP_END_SIZE: usize = 22;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ArchiveFormat {
+    #[default]
+    Tar,
+    Zip,
+}
+
+impl ArchiveFormat {
+    pub const ALL: [ArchiveFormat; 2] = [ArchiveFormat::Tar, ArchiveFormat::Zip];
+
+    /// One archive holding every file under its name. The buffer is allocated
+    /// at its final size, so growing it leaves no copies of the secrets behind.
+    pub fn pack(self, files: &[(ArcStr, SecretBytes)]) -> io::Result<Zeroizing<Vec<u8>>> {
+        let size = self.size(
+            files
+                .iter()
+                .map(|(name, content)| (name.len(), content.len())),
+        );
+        let mut archive = Zeroizing::new(Vec::with_capacity(size));
+        match self {
+            ArchiveFormat::Tar => {
+                let mut builder = tar::Builder::new(&mut *archive);
+                for (name, content) in files {
+                    let mut header = tar::Header::new_gnu();
+                    header.set_size(content.len() as u64);
+                    header.set_mode(0o600);
+                    builder.append_data(&mut header, name.as_str(), &content[..])?;
+                }
+                builder.finish()?;
+            }
+            ArchiveFormat::Zip => {
+                // The default date is 1980-01-01, the earliest zip can store.
+                // A real date would only tell when the sheet was made.
+                let options = SimpleFileOptions::default()
+                    .compression_method(zip::CompressionMethod::Stored)
+                    .unix_permissions(0o600);
+                let mut writer = zip::ZipWriter::new(io::Cursor::new(&mut *archive));
+                for (name, content) in files {
+                    writer.start_file(name.as_str(), options)?;
+                    writer.write_all(content)?;
+                }
+                writer.finish()?;
+            }
+        }
+        Ok(archive)
+    }
+
+    /// Size of the archive of files with the given name and content lengths
+    pub fn size(self, files: impl Iterator<Item = (usize, usize)>) -> usize {
+        match self {
+            // The archive ends with two empty blocks
+            ArchiveFormat::Tar => {
+                files
+                    .map(|(_, len)| TAR_BLOCK + len.div_ceil(TAR_BLOCK) * TAR_BLOCK)
+                    .sum::<usize>()
+                    + 2 * TAR_BLOCK
+            }
+            ArchiveFormat::Zip => {
+                files
+                    .map(|(name, len)| {
+                        ZIP_LOCAL_HEADER_SIZE + ZIP_CENTRAL_HEADER_SIZE + 2 * name + len
+                    })
+                    .sum::<usize>()
+                    + ZIP_END_SIZE
+            }
+        }
+    }
+
+    /// The format of `data`, if it is an archive at all
+    pub fn detect(data: &[u8]) -> Option<ArchiveFormat> {
+        if data.starts_with(ZIP_MAGIC) {
+            Some(ArchiveFormat::Zip)
+        } else if data.get(257..262) == Some(b"ustar") {
+            Some(ArchiveFormat::Tar)
+        } else {
+            None
+        }
+    }
+
+    /// Name and size of every file in the archive `data`, `None` if it can't
+    /// be read as this format
+    pub fn list(self, data: &[u8]) -> Option<Vec<(String, usize)>> {
+        match self {
+            ArchiveFormat::Tar => tar::Archive::new(data)
+                .entries()
+                .ok()?
+                .map(|entry| {
+                    let entry = entry.ok()?;
+                    let name = entry.path().ok()?.to_string_lossy().into_owned();
+                    Some((name, entry.size() as usize))
+                })
+                .collect(),
+            ArchiveFormat::Zip => {
+                let mut archive = zip::ZipArchive::new(io::Cursor::new(data)).ok()?;
+                (0..archive.len())
+                    .map(|index| {
+                        let file = archive.by_index_raw(index).ok()?;
+                        Some((file.name().to_string(), file.size() as usize))
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+impl fmt::Display for ArchiveFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ArchiveFormat::Tar => "tar",
+            ArchiveFormat::Zip => "zip",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn files() -> Vec<(ArcStr, SecretBytes)> {
+        vec![
+            (
+                "hello.txt".into(),
+                Arc::new(Zeroizing::new(b"hello\n".to_vec())),
+            ),
+            (
+                "schlüssel.bin".into(),
+                Arc::new(Zeroizing::new(vec![7; 1000])),
+            ),
+        ]
+    }
+
+    #[test]
+    fn pack_and_list() {
+        for format in ArchiveFormat::ALL {
+            let archive = format.pack(&files()).unwrap();
+            assert_eq!(ArchiveFormat::detect(&archive), Some(format));
+            assert_eq!(
+                format.list(&archive).unwrap(),
+                [
+                    ("hello.txt".to_string(), 6),
+                    ("schlüssel.bin".to_string(), 1000)
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn size_matches_packed_archive() {
+        let files = files();
+        for format in ArchiveFormat::ALL {
+            let size = format.size(
+                files
+                    .iter()
+                    .map(|(name, content)| (name.len(), content.len())),
+            );
+            assert_eq!(format.pack(&files).unwrap().len(), size, "{format}");
+        }
+    }
+
+    #[test]
+    fn list_zip_from_zip_tool() {
+        // Made with Info-ZIP's `zip -r`, which compresses and adds extra fields
+        let archive = include_bytes!("testdata/two-files.zip");
+        assert_eq!(ArchiveFormat::detect(archive), Some(ArchiveFormat::Zip));
+        assert_eq!(
+            ArchiveFormat::Zip.list(archive).unwrap(),
+            [
+                ("hello.txt".to_string(), 6),
+                ("keys/ssh.key".to_string(), 300)
+            ]
+        );
+    }
+
+    #[test]
+    fn not_an_archive() {
+        assert_eq!(ArchiveFormat::detect(b"hello"), None);
+        assert_eq!(ArchiveFormat::Zip.list(b"PK\x03\x04 but cut short"), None);
+    }
+}
@@ -0,0 +1,50 @@
+//! Splitting a multi-secret input into one record per PDF.
+//!
+//! Records are separated by blank lines. A record whose first line looks like
+//! `title: secret` uses that title for its sheet, otherwise the form title is used.
+
+use age::secrecy::zeroize::Zeroizing;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub title: Option<String>,
+    pub secret: Zeroizing<String>,
+}
+
+/// Parse blank-line-separated records, skipping empty ones
+pub fn parse(input: &str) -> Vec<Record> {
+    let mut records = vec![];
+    let mut lines = vec![];
+    for line in input.lines().chain(std::iter::once("")) {
+        if !line.trim().is_empty() {
+            lines.push(line);
+        } else if !lines.is_empty() {
+            records.extend(record(&lines));
+            lines.clear();
+        }
+    }
+    records
+}
+
+fn record(lines: &[&str]) -> Option<Record> {
+    let (first, rest) = lines.split_first()?;
+    // Only a colon followed by whitespace or the line end starts a title, so
+    // secrets like URLs are left alone
+    let (title, first) = match first.split_once(':') {
+        Some((title, secret))
+            if !title.trim().is_empty()
+                && (secret.is_empty() || secret.starts_with(char::is_whitespace)) =>
+        {
+            (Some(title.trim().to_string()), secret.trim())
+        }
+        _ => (None, *first),
+    };
+    let secret = Zeroizing::new(
+        std::iter::once(first)
+            .chain(rest.iter().copied())
+            .collect::<Vec<_>>()
+            .join("\n"),
+    );
+    let secret = Zeroizing::new(secret.trim().to_string());
+    (!secret.is_empty()).then_some(Record { title, secret })
+}
@@ -0,0 +1,158 @@
+//! Estimates of how much data fits in a PaperAge QR code.
+
+use paper_age::page::PageSize;
+use qrcode::{EcLevel, QrCode};
+use std::fmt;
+
+/// Size of the age header for a single scrypt recipient plus the payload nonce
+const AGE_HEADER_SIZE: usize = 167;
+
+/// Size of the authentication tag appended to every payload chunk
+const AGE_TAG_SIZE: usize = 16;
+
+/// Plaintext size of a single age payload chunk
+const AGE_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Size of the `BEGIN` armor line including its line break
+const ARMOR_BEGIN: usize = 35;
+
+/// Size of the `END` armor line including its line break
+const ARMOR_END: usize = 33;
+
+/// Size of the `BEGIN`/`END` armor lines including their line breaks
+const ARMOR_OVERHEAD: usize = ARMOR_BEGIN + ARMOR_END;
+
+/// Column width of the armored base64 body
+const ARMOR_COLUMNS: usize = 64;
+
+/// Byte mode capacity of a version 40 QR code with the lowest error correction
+const QR_CAPACITY: usize = 2953;
+
+/// QR error correction level. `paper_age` always uses the highest level the
+/// data fits in, so the selected level is the lowest one that is accepted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum EccLevel {
+    #[default]
+    L,
+    M,
+    Q,
+    H,
+}
+
+impl EccLevel {
+    pub const ALL: [EccLevel; 4] = [EccLevel::L, EccLevel::M, EccLevel::Q, EccLevel::H];
+
+    /// Byte mode capacity of a version 40 QR code at this level
+    pub fn capacity(self) -> usize {
+        match self {
+            EccLevel::L => QR_CAPACITY,
+            EccLevel::M => 2331,
+            EccLevel::Q => 1663,
+            EccLevel::H => 1273,
+        }
+    }
+
+    /// The level `paper_age` ends up using for `data`, or `None` if it doesn't fit at all
+    pub fn chosen_for(data: &[u8]) -> Option<EccLevel> {
+        EccLevel::ALL.into_iter().rev().find(|level| {
+            let level = match level {
+                EccLevel::L => EcLevel::L,
+                EccLevel::M => EcLevel::M,
+                EccLevel::Q => EcLevel::Q,
+                EccLevel::H => EcLevel::H,
+            };
+            QrCode::with_error_correction_level(data, level).is_ok()
+        })
+    }
+}
+
+impl fmt::Display for EccLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let recovery = match self {
+            EccLevel::L => 7,
+            EccLevel::M => 15,
+            EccLevel::Q => 25,
+            EccLevel::H => 30,
+        };
+        write!(f, "{self:?} ({recovery}%)")
+    }
+}
+
+/// Fraction of the capacity after which the user is warned in advance
+pub const WARNING_THRESHOLD: f32 = 0.9;
+
+/// Fraction of the capacity after which the gauge turns amber, a little
+/// before the warning so it can be seen coming
+pub const GAUGE_THRESHOLD: f32 = 0.8;
+
+/// Largest file accepted as a secret. Encryption only adds to the size, so a
+/// file above what the QR codes of the most split sheets hold can never fit
+/// and is refused before it is stored.
+pub const MAX_FILE_SIZE: usize =
+    crate::split::MAX_PARTS * (QR_CAPACITY - crate::split::HEADER_SIZE);
+
+/// Size of the age ciphertext in the QR code for a plaintext of `len` bytes,
+/// ASCII-armored, in the [`crate::pdf::compact`] form or binary
+pub fn payload_size(len: usize, armor: bool, binary_qr: bool) -> usize {
+    let base64 = base64_size(len);
+    if binary_qr {
+        binary_size(len)
+    } else if armor {
+        base64 + base64.div_ceil(ARMOR_COLUMNS) + ARMOR_OVERHEAD
+    } else {
+        base64
+    }
+}
+
+/// Size of the raw age ciphertext
+fn binary_size(len: usize) -> usize {
+    let chunks = len / AGE_CHUNK_SIZE + 1;
+    AGE_HEADER_SIZE + len + chunks * AGE_TAG_SIZE
+}
+
+/// Size of the base64 body of the armor without line breaks
+fn base64_size(len: usize) -> usize {
+    binary_size(len).div_ceil(3) * 4
+}
+
+/// Size of the raw ciphertext a [`crate::pdf::compact`] payload decodes to
+pub fn binary_len(compact: &str) -> usize {
+    let padding = compact.bytes().rev().take_while(|&b| b == b'=').count();
+    compact.len() / 4 * 3 - padding
+}
+
+/// Number of sheets [`crate::split::split`] cuts the ciphertext for a
+/// plaintext of `len` bytes into, filling every QR code with whole lines.
+/// Split payloads are always armored, as the parts are cut at its line breaks.
+pub fn sheets_needed(len: usize, page_size: &PageSize, ecc_level: EccLevel) -> usize {
+    let room = qr_capacity(page_size, ecc_level) - crate::split::HEADER_SIZE;
+    let base64 = base64_size(len);
+    let body = std::iter::repeat_n(ARMOR_COLUMNS + 1, base64 / ARMOR_COLUMNS)
+        .chain((!base64.is_multiple_of(ARMOR_COLUMNS)).then_some(base64 % ARMOR_COLUMNS + 1));
+    let mut sheets = 1;
+    let mut used = 0;
+    for line in std::iter::once(ARMOR_BEGIN).chain(body).chain([ARMOR_END]) {
+        if used + line > room {
+            sheets += 1;
+            used = 0;
+        }
+        used += line;
+    }
+    sheets
+}
+
+/// Number of payload bytes a QR code on the given page can hold at the
+/// given error correction level.
+///
+/// PaperAge scales the code to a fixed area of the page, so every page size
+/// gets the largest QR code.
+pub fn qr_capacity(page_size: &PageSize, ecc_level: EccLevel) -> usize {
+    match page_size {
+        PageSize::A4 | PageSize::Letter => ecc_level.capacity(),
+    }
+}
+
+/// Fraction of the QR capacity used by a payload of `len` bytes, see [`payload_size`]
+pub fn usage(len: usize, page_size: &PageSize, ecc_level: EccLevel) -> f32 {
+    len as f32 / qr_capacity(page_size, ecc_level) as f32
+}
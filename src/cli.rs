@@ -0,0 +1,172 @@
+//! Headless generation for scripts, sharing the PDF pipeline with the GUI.
+
+use age::secrecy::zeroize::Zeroizing;
+use anyhow::{Context, Result, anyhow, bail};
+use clap::{CommandFactory, Parser};
+use paper_age::page::PageSize;
+use std::io::Read;
+use std::path::PathBuf;
+
+use crate::capacity::{self, EccLevel};
+use crate::i18n::tr;
+use crate::pdf::{self, Encryption, Layout};
+
+/// Generate a PaperAge PDF without opening the window
+#[derive(Debug, Parser)]
+#[command(version)]
+struct Args {
+    /// File with the secret to encrypt
+    #[arg(long, required_unless_present = "stdin")]
+    secret_file: Option<PathBuf>,
+
+    /// Read the secret from standard input instead of a file
+    #[arg(long, conflicts_with = "secret_file")]
+    stdin: bool,
+
+    /// File with the passphrase on a single line
+    #[arg(long)]
+    passphrase_file: PathBuf,
+
+    /// Title printed at the top of the page
+    #[arg(long, default_value = "")]
+    title: String,
+
+    /// Label of the notes field
+    #[arg(long, default_value = "")]
+    notes_label: String,
+
+    /// Short identifier printed before the notes label
+    #[arg(long, default_value = "")]
+    qr_caption: String,
+
+    /// Author in the PDF properties
+    #[arg(long, default_value = "")]
+    author: String,
+
+    /// Subject in the PDF properties
+    #[arg(long, default_value = "")]
+    subject: String,
+
+    /// Paper size of the sheet
+    #[arg(long, value_enum, default_value_t = PageSize::A4)]
+    page_size: PageSize,
+
+    /// Put today's date next to the notes label
+    #[arg(long)]
+    date_stamp: bool,
+
+    /// Font file for the title and notes label, for scripts the default font lacks
+    #[arg(long)]
+    font: Option<PathBuf>,
+
+    /// Add a page with recovery instructions before the sheet
+    #[arg(long)]
+    instructions: bool,
+
+    /// Leave age's ASCII armor out of the QR code to fit larger secrets
+    #[arg(long)]
+    compact: bool,
+
+    /// Put the raw ciphertext into the QR code to fit even larger secrets.
+    /// Scanning it needs a reader that keeps binary data.
+    #[arg(long)]
+    binary_qr: bool,
+
+    /// Where to write the PDF
+    #[arg(long)]
+    output: PathBuf,
+}
+
+/// Whether one of the options above, `--help` or `--version` was given. Other
+/// arguments, like the ones some launchers add, still open the window.
+pub fn requested() -> bool {
+    let command = Args::command();
+    std::env::args_os().skip(1).any(|arg| {
+        let Some(arg) = arg.to_str() else {
+            return false;
+        };
+        let Some(option) = arg.strip_prefix("--") else {
+            return matches!(arg, "-h" | "-V");
+        };
+        let name = option.split_once('=').map_or(option, |(name, _)| name);
+        matches!(name, "help" | "version")
+            || command
+                .get_arguments()
+                .any(|argument| argument.get_long() == Some(name))
+    })
+}
+
+/// Write the PDF and print the ciphertext fingerprint
+pub fn run() -> Result<()> {
+    let args = Args::parse();
+    let (input, source): (Box<dyn Read>, String) = match &args.secret_file {
+        Some(path) => (
+            Box::new(
+                std::fs::File::open(path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?,
+            ),
+            path.display().to_string(),
+        ),
+        None => (Box::new(std::io::stdin()), "the standard input".to_string()),
+    };
+    // One byte more than accepted is enough to tell the input is too large
+    let mut secret = Zeroizing::new(vec![]);
+    input
+        .take(capacity::MAX_FILE_SIZE as u64 + 1)
+        .read_to_end(&mut secret)
+        .with_context(|| format!("Failed to read {source}"))?;
+    if secret.is_empty() {
+        bail!(tr("warning-secret-empty"));
+    }
+    if secret.len() > capacity::MAX_FILE_SIZE {
+        bail!(tr("warning-secret-too-large"));
+    }
+    let passphrase = std::fs::read(&args.passphrase_file)
+        .with_context(|| format!("Failed to read {}", args.passphrase_file.display()))?;
+    let passphrase = crate::parse_passphrase_file(passphrase).map_err(|err| anyhow!(err))?;
+    let font = match &args.font {
+        Some(path) => {
+            let bytes = std::fs::read(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            Some(
+                pdf::Font::parse(&bytes)
+                    .with_context(|| format!("{} is not a font", path.display()))?,
+            )
+        }
+        None => None,
+    };
+    let layout = Layout {
+        title: args.title,
+        notes_label: crate::truncate(&args.notes_label, crate::NOTES_LABEL_MAX_LEN).to_string(),
+        qr_caption: crate::truncate(&args.qr_caption, crate::NOTES_LABEL_MAX_LEN).to_string(),
+        page_size: args.page_size,
+        grid: false,
+        min_ecc: EccLevel::default(),
+        print_armor: true,
+        armor: !args.compact,
+        binary_qr: args.binary_qr,
+        date_stamp: args.date_stamp,
+        font,
+        instructions: args
+            .instructions
+            .then(|| crate::instructions(&args.notes_label, !args.compact, args.binary_qr, None)),
+        author: args.author,
+        subject: args.subject,
+    };
+    if let Some(c) = layout.font.as_ref().and_then(|font| {
+        font.missing_glyph(&layout.title).or_else(|| {
+            font.missing_glyph(&pdf::notes_line(
+                &layout.notes_label,
+                &layout.qr_caption,
+                layout.date_stamp,
+            ))
+        })
+    }) {
+        eprintln!("Warning: the font has no glyph for '{c}', using the default font");
+    }
+    let sheet = pdf::create(layout, &secret, Encryption::Passphrase(passphrase), |_| {})?;
+    std::fs::write(&args.output, sheet.pdf)
+        .with_context(|| format!("Failed to write {}", args.output.display()))?;
+    println!("{}", sheet.fingerprint);
+    Ok(())
+}
@@ -0,0 +1,83 @@
+//! Reading a generated PaperAge PDF back and decrypting its payload.
+
+use std::io::Read;
+
+use age::armor::ArmoredReader;
+use age::secrecy::SecretString;
+use age::secrecy::zeroize::Zeroizing;
+use anyhow::{Context, Result, anyhow, bail};
+
+use crate::pdf;
+
+/// Render scales used to make QR modules large enough for detection. The
+/// detector misses some codes at one scale and finds them at another, which
+/// matters most for split secrets where every part has to be read.
+const RENDER_SCALES: [f32; 3] = [3.0, 3.5, 5.0];
+
+/// Extract the age payload from the QR code on the sheet, the last page after
+/// the instructions if there are any. Binary codes come out [`pdf::compact`].
+pub fn read_payload(pdf: &[u8]) -> Result<String> {
+    let pdf = hayro::hayro_syntax::Pdf::new(pdf.to_vec()).map_err(|err| anyhow!("{err:?}"))?;
+    let page = pdf.pages().last().context("PDF has no pages")?;
+    RENDER_SCALES
+        .into_iter()
+        .find_map(|scale| read_qr_code(page, scale))
+        .context("No QR code found")
+}
+
+fn read_qr_code(page: &hayro::hayro_syntax::page::Page, scale: f32) -> Option<String> {
+    let pixmap = hayro::render(
+        page,
+        &hayro::RenderCache::new(),
+        &Default::default(),
+        &Default::default(),
+        &hayro::PixmapSettings {
+            x_scale: scale,
+            y_scale: scale,
+            bg_color: hayro::vello_cpu::color::palette::css::WHITE,
+        },
+    );
+    let width = usize::from(pixmap.width());
+    let pixels = pixmap.data_as_u8_slice();
+    let mut image =
+        rqrr::PreparedImage::prepare_from_greyscale(width, usize::from(pixmap.height()), |x, y| {
+            let pixel = &pixels[(y * width + x) * 4..][..3];
+            ((u16::from(pixel[0]) + u16::from(pixel[1]) + u16::from(pixel[2])) / 3) as u8
+        });
+    image.detect_grids().iter().find_map(|grid| {
+        let mut data = vec![];
+        grid.decode_to(&mut data).ok()?;
+        pdf::from_binary(&data).or_else(|| String::from_utf8(data).ok())
+    })
+}
+
+/// Decrypt an armored or [`pdf::compact`] age payload with a passphrase
+pub fn decrypt(payload: &str, passphrase: SecretString) -> Result<Zeroizing<Vec<u8>>> {
+    let payload = pdf::armor(payload);
+    let decryptor = age::Decryptor::new(ArmoredReader::new(payload.as_bytes()))?;
+    let identity = age::scrypt::Identity::new(passphrase);
+    let mut reader = decryptor.decrypt(std::iter::once(&identity as &dyn age::Identity))?;
+    let mut plaintext = Zeroizing::new(vec![]);
+    reader.read_to_end(&mut plaintext)?;
+    Ok(plaintext)
+}
+
+/// Read a freshly generated sheet back, making sure its QR code holds `payload`
+/// and, when the passphrase is known, that it decrypts to `secret`
+pub fn verify(
+    pdf: &[u8],
+    payload: &str,
+    secret: &[u8],
+    passphrase: Option<SecretString>,
+) -> Result<()> {
+    if read_payload(pdf)? != payload {
+        bail!("The QR code doesn't hold the encrypted payload");
+    }
+    // Only the recipients' private keys could decrypt a sheet for public keys
+    if let Some(passphrase) = passphrase
+        && *decrypt(payload, passphrase)? != secret
+    {
+        bail!("The decrypted payload differs from the secret");
+    }
+    Ok(())
+}
@@ -0,0 +1,55 @@
+//! Character encodings a text secret can be converted to before encrypting.
+
+use age::secrecy::zeroize::Zeroizing;
+use encoding_rs::WINDOWS_1252;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TextEncoding {
+    #[default]
+    Utf8,
+    Utf16Le,
+    Latin1,
+}
+
+impl TextEncoding {
+    pub const ALL: [TextEncoding; 3] = [
+        TextEncoding::Utf8,
+        TextEncoding::Utf16Le,
+        TextEncoding::Latin1,
+    ];
+
+    /// Bytes of `text` in this encoding, or the first character it can't represent
+    pub fn encode(self, text: &str) -> Result<Zeroizing<Vec<u8>>, char> {
+        match self {
+            TextEncoding::Utf8 => Ok(Zeroizing::new(text.as_bytes().to_vec())),
+            // `encoding_rs` follows the Encoding Standard, which only decodes
+            // UTF-16, so the standard library does the encoding
+            TextEncoding::Utf16Le => Ok(Zeroizing::new(
+                text.encode_utf16().flat_map(u16::to_le_bytes).collect(),
+            )),
+            // Like browsers, Latin-1 means its Windows-1252 superset
+            TextEncoding::Latin1 => {
+                let mut buffer = [0; 4];
+                if let Some(c) = text
+                    .chars()
+                    .find(|c| WINDOWS_1252.encode(c.encode_utf8(&mut buffer)).2)
+                {
+                    return Err(c);
+                }
+                Ok(Zeroizing::new(WINDOWS_1252.encode(text).0.into_owned()))
+            }
+        }
+    }
+}
+
+impl fmt::Display for TextEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            TextEncoding::Utf8 => "UTF-8",
+            TextEncoding::Utf16Le => "UTF-16LE",
+            TextEncoding::Latin1 => "Latin-1 (Windows-1252)",
+        })
+    }
+}
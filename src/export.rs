@@ -0,0 +1,52 @@
+//! Outputs other than the formatted PDF sheet.
+//!
+//! These go through the same `paper_age` encryption and QR code encoding as
+//! the PDF, so a QR image can be decoded and decrypted like a scanned sheet.
+
+use anyhow::{Context, Result};
+use resvg::tiny_skia::{Color, Pixmap, Transform};
+use resvg::usvg::{Options, ShapeRendering, Tree};
+
+use crate::pdf;
+
+/// Width and height of the QR code itself in the exported image
+const QR_PNG_SIZE: u32 = 1024;
+
+/// White border around the QR code, scanners need a quiet zone to find it
+const QR_PNG_MARGIN: u32 = 64;
+
+/// Width and height of the QR code in the thumbnail shown in the form
+const QR_THUMBNAIL_SIZE: u32 = 240;
+
+/// Render the [`pdf::qr_data`] of a payload as a PNG QR code
+pub fn qr_png(data: &[u8]) -> Result<Vec<u8>> {
+    render_qr(data, QR_PNG_SIZE, QR_PNG_MARGIN)
+}
+
+/// Render a small preview of the QR code, with a quiet zone in proportion
+pub fn qr_thumbnail(data: &[u8]) -> Result<Vec<u8>> {
+    render_qr(
+        data,
+        QR_THUMBNAIL_SIZE,
+        QR_PNG_MARGIN * QR_THUMBNAIL_SIZE / QR_PNG_SIZE,
+    )
+}
+
+fn render_qr(data: &[u8], size: u32, margin: u32) -> Result<Vec<u8>> {
+    let svg = pdf::qr_svg(data)?;
+    let options = Options {
+        shape_rendering: ShapeRendering::CrispEdges,
+        ..Default::default()
+    };
+    let tree = Tree::from_str(&svg, &options)?;
+    let scale = size as f32 / tree.size().width().max(tree.size().height());
+    let mut pixmap =
+        Pixmap::new(size + 2 * margin, size + 2 * margin).context("Invalid image size")?;
+    pixmap.fill(Color::WHITE);
+    resvg::render(
+        &tree,
+        Transform::from_scale(scale, scale).post_translate(margin as f32, margin as f32),
+        &mut pixmap.as_mut(),
+    );
+    Ok(pixmap.encode_png()?)
+}
@@ -0,0 +1,106 @@
+//! Interface translations.
+//!
+//! Each language is an embedded Fluent resource under `assets/locales`.
+//! Messages missing from a translation fall back to English.
+
+use fluent::concurrent::FluentBundle;
+use fluent::{FluentArgs, FluentResource};
+use std::fmt;
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use unic_langid::{LanguageIdentifier, langid};
+
+use crate::Assets;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::German];
+
+    pub fn id(self) -> LanguageIdentifier {
+        match self {
+            Language::English => langid!("en"),
+            Language::German => langid!("de"),
+        }
+    }
+
+    fn resource(self) -> &'static [u8] {
+        match self {
+            Language::English => Assets.locales().en().content(),
+            Language::German => Assets.locales().de().content(),
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+        })
+    }
+}
+
+static BUNDLES: LazyLock<Vec<FluentBundle<FluentResource>>> =
+    LazyLock::new(|| Language::ALL.into_iter().map(bundle).collect());
+
+/// Index of the current language in [`Language::ALL`]. It is one setting for
+/// the whole app, so every window shows the same language.
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+
+fn bundle(language: Language) -> FluentBundle<FluentResource> {
+    let source = String::from_utf8_lossy(language.resource()).into_owned();
+    let resource = FluentResource::try_new(source).expect("Valid Fluent resource");
+    let mut bundle = FluentBundle::new_concurrent(vec![language.id()]);
+    // Unicode isolation marks around arguments would show up as boxes
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(resource)
+        .expect("No duplicate Fluent messages");
+    bundle
+}
+
+/// Switch the language used by [`tr`] and [`tr_args`]
+pub fn set_language(language: Language) {
+    let index = Language::ALL
+        .iter()
+        .position(|l| *l == language)
+        .unwrap_or_default();
+    CURRENT.store(index, Ordering::Relaxed);
+}
+
+/// The language used by [`tr`] and [`tr_args`]
+pub fn language() -> Language {
+    Language::ALL[CURRENT.load(Ordering::Relaxed)]
+}
+
+/// Translate a message in the current language
+pub fn tr(key: &str) -> String {
+    format(key, None)
+}
+
+/// Translate a message with arguments in the current language
+pub fn tr_args(key: &str, args: &FluentArgs) -> String {
+    format(key, Some(args))
+}
+
+fn format(key: &str, args: Option<&FluentArgs>) -> String {
+    let current = &BUNDLES[CURRENT.load(Ordering::Relaxed)];
+    [current, &BUNDLES[0]]
+        .into_iter()
+        .find_map(|bundle| {
+            let pattern = bundle.get_message(key)?.value()?;
+            let mut errors = vec![];
+            Some(
+                bundle
+                    .format_pattern(pattern, args, &mut errors)
+                    .into_owned(),
+            )
+        })
+        .unwrap_or_else(|| key.to_string())
+}
@@ -1,17 +1,56 @@
+mod archive;
+mod batch;
+mod capacity;
+#[cfg(not(target_arch = "wasm32"))]
+mod cli;
+mod decrypt;
+mod encoding;
+mod export;
+mod i18n;
+mod passphrase;
+mod pdf;
+mod settings;
+mod split;
+#[cfg(feature = "tray")]
+mod tray;
+mod windows;
+
+use age::secrecy::zeroize::{Zeroize, Zeroizing};
 use age::secrecy::{ExposeSecret, SecretString};
+use age::x25519;
 use anyhow::Result;
 use arcstr::ArcStr;
 use embed_it::Embed;
+use fluent::fluent_args;
 use iced::advanced::svg::Handle;
+use iced::futures::channel::mpsc;
+use iced::futures::{SinkExt, Stream};
+use iced::keyboard::{self, key};
+use iced::time::Instant;
+use iced::widget::scrollable::RelativeOffset;
 use iced::widget::{
-    Space, button, column, container, pick_list, row, scrollable, svg, text, text_editor,
-    text_input, toggler,
+    Column, Space, Text, button, center, column, container, image, mouse_area, opaque, operation,
+    pick_list, progress_bar, row, scrollable, slider, stack, svg, text, text_editor, text_input,
+    toggler,
 };
-use iced::{Element, Fill, Length, Task, Theme};
-use paper_age::{convenience::create_pdf, page::PageSize};
+use iced::{Color, Element, Fill, Length, Size, Subscription, Task, Theme, theme, window};
+use paper_age::convenience::PaperAgeError;
+use paper_age::page::PageSize;
+use rand::seq::SliceRandom;
 use rfd::FileHandle;
-use std::io::Cursor;
+use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
+
+use archive::ArchiveFormat;
+use capacity::EccLevel;
+use encoding::TextEncoding;
+use i18n::{Language, tr, tr_args};
+use passphrase::{Manual, PassphraseError, PassphraseSource, Weakness};
+use pdf::Encryption;
+use settings::{Preferences, Profile};
+use windows::Windows;
 
 #[derive(Embed)]
 #[embed(path = "$CARGO_MANIFEST_DIR/assets", support_alt_separator)]
@@ -25,397 +64,4687 @@ fn main() -> iced::Result {
     }
 
     #[cfg(not(target_arch = "wasm32"))]
-    tracing_subscriber::fmt::init();
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .init();
+
+    #[cfg(not(target_arch = "wasm32"))]
+    if cli::requested() {
+        if let Err(err) = cli::run() {
+            eprintln!("Error: {err:#}");
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
 
-    iced::application(App::default, App::update, App::view)
-        .theme(Theme::CatppuccinMocha)
-        .centered()
+    iced::daemon(Windows::new, Windows::update, Windows::view)
+        .title(Windows::title)
+        .theme(Windows::theme)
+        .subscription(Windows::subscription)
         .run()
 }
 
 type ArcBytes = Arc<[u8]>;
 
+/// Plaintext secret bytes, wiped from memory once the last copy is dropped
+type SecretBytes = Arc<Zeroizing<Vec<u8>>>;
+
+fn secret_bytes(content: Vec<u8>) -> SecretBytes {
+    Arc::new(Zeroizing::new(content))
+}
+
+/// Where batch mode writes its PDFs. The web has no folders, so every sheet
+/// goes through its own download.
+#[cfg(not(target_arch = "wasm32"))]
+type BatchFolder = PathBuf;
+#[cfg(target_arch = "wasm32")]
+type BatchFolder = ();
+
+/// Page sizes supported by `paper_age`. A5 and Legal are not offered upstream,
+/// so they are left out rather than mapped onto a different layout.
+const PAGE_SIZES: [PageSize; 2] = [PageSize::A4, PageSize::Letter];
+
+/// Size of regular text before the user's text scale is applied
+const TEXT_SIZE: f32 = 16.0;
+
+/// Number of secret editor states kept for undo
+const UNDO_LIMIT: usize = 100;
+
+/// Longest notes label that still fits next to the notes field on the page
+const NOTES_LABEL_MAX_LEN: usize = 32;
+
+/// Number of recent titles and notes labels offered again
+const HISTORY_LIMIT: usize = 8;
+
+/// Pause after the last edit before the previewed ciphertext is recomputed
+const CIPHERTEXT_DELAY: Duration = Duration::from_millis(400);
+
+/// Window width from which the form is split into two columns
+const WIDE_LAYOUT_WIDTH: f32 = 900.0;
+
+/// Window width below which the outer padding shrinks to leave room for the form
+const COMPACT_LAYOUT_WIDTH: f32 = 480.0;
+
+/// Pause after the last edit before a field is checked for problems
+const VALIDATION_DELAY: Duration = Duration::from_millis(600);
+
+/// How long loading the secret from a URL may take before giving up
+#[cfg(feature = "url")]
+const URL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long the blob URL of a download stays valid for the browser to read it
+#[cfg(target_arch = "wasm32")]
+const DOWNLOAD_REVOKE_DELAY_MS: i32 = 60_000;
+
+/// Widget ids of the fields that receive focus
+const SECRET_EDITOR_ID: &str = "secret-editor";
+const PASSPHRASE_ID: &str = "passphrase";
+const RECIPIENTS_ID: &str = "recipients";
+const CONTENT_ID: &str = "content";
+
+/// Range offered for the text scale preference
+const TEXT_SCALE_RANGE: std::ops::RangeInclusive<f32> = 0.8..=2.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AppMode {
+    #[default]
+    Encrypt,
+    Decrypt,
+}
+
+/// Theme preference, either a fixed theme or following the OS appearance
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppTheme {
+    System,
+    Fixed(Theme),
+}
+
+impl AppTheme {
+    /// Name used in the preferences file, the system option is not a [`Theme`]
+    pub const SYSTEM: &str = "System";
+
+    fn all() -> Vec<AppTheme> {
+        std::iter::once(AppTheme::System)
+            .chain(Theme::ALL.iter().cloned().map(AppTheme::Fixed))
+            .collect()
+    }
+}
+
+impl std::fmt::Display for AppTheme {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            AppTheme::System => f.write_str(&tr("theme-system")),
+            AppTheme::Fixed(theme) => theme.fmt(f),
+        }
+    }
+}
+
+/// Page size as listed in the picker, named together with its dimensions
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PageSizeOption(PageSize);
+
+impl PageSizeOption {
+    fn name(&self) -> &'static str {
+        match self.0 {
+            PageSize::A4 => "A4",
+            PageSize::Letter => "Letter",
+        }
+    }
+}
+
+impl std::fmt::Display for PageSizeOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let dimensions = match self.0 {
+            PageSize::A4 => "210×297 mm",
+            PageSize::Letter => "8.5×11 in",
+        };
+        write!(f, "{} ({dimensions})", self.name())
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Secret {
+    /// Editor text, converted to bytes once it is validated
+    Text(Zeroizing<String>, TextEncoding),
+    Files(Vec<(ArcStr, SecretBytes)>, ArchiveFormat),
+}
+
+/// Key material as entered in the form, before validation
+#[derive(Debug, Clone)]
+enum KeyInput {
+    Passphrase(Arc<dyn PassphraseSource>),
+    Recipients(ArcStr),
+}
+
+/// Result of one sheet in batch mode or of one separately encrypted file
+#[derive(Debug, Clone)]
+pub struct BatchOutcome {
+    label: ArcStr,
+    saved_to: Result<ArcStr, ArcStr>,
+}
+
 struct App {
+    mode: AppMode,
     title: ArcStr,
     passphrase: SecretString,
+    passphrase_confirm: SecretString,
+    passphrase_score: Option<u8>,
+    /// Name of the file the passphrase was loaded from, if it wasn't typed
+    passphrase_file: Option<ArcStr>,
+    show_passphrase: bool,
+    passphrase_words: usize,
+    passphrase_copied: bool,
+    /// Short-lived confirmation at the bottom of the window
+    toast: Option<ArcStr>,
+    use_recipients: bool,
+    recipients: ArcStr,
     secret_content: text_editor::Content,
-    secret_file_name: Option<ArcStr>,
-    secret_file_content: Option<ArcBytes>,
+    secret_undo: Vec<Zeroizing<String>>,
+    secret_redo: Vec<Zeroizing<String>>,
+    secret_typing: bool,
+    secret_file_names: Vec<ArcStr>,
+    secret_file_contents: Vec<SecretBytes>,
     secret_file_loading: bool,
+    secret_file_hovered: bool,
+    /// Address to load a secret file from, see [`App::read_secret_url`]
+    #[cfg(feature = "url")]
+    secret_url: String,
+    #[cfg(feature = "url")]
+    secret_url_loading: bool,
     is_file_secret: bool,
+    batch: bool,
+    /// Encrypt every selected file to its own sheet instead of one archive
+    separate_files: bool,
+    batch_outcomes: Vec<BatchOutcome>,
+    secret_size: usize,
+    secret_chars: usize,
+    secret_bytes: usize,
     notes_label: ArcStr,
+    /// Printed before the notes label, see [`pdf::notes_line`]
+    qr_caption: ArcStr,
+    author: ArcStr,
+    subject: ArcStr,
+    notes_warning: Option<ArcStr>,
+    qr_caption_warning: Option<ArcStr>,
+    output_name: Option<ArcStr>,
     show_extra: bool,
+    show_help: bool,
+    show_about: bool,
     secret_warning: Option<ArcStr>,
+    confirm_double_encrypt: bool,
+    /// Set once the warning about a weak passphrase was shown, see [`passphrase::weakness`]
+    confirm_weak_passphrase: bool,
     passphrase_warning: Option<ArcStr>,
     generate_warning: Option<ArcStr>,
+    generate_error_details: Option<ArcStr>,
     is_generating: bool,
+    /// Results of file reads and the like that arrived while generating,
+    /// applied once it is done
+    deferred: Vec<Message>,
+    generate_progress: Option<f32>,
+    spinner_rotation: f32,
     page_size: PageSize,
+    grid: bool,
+    ecc_level: EccLevel,
+    print_plaintext: bool,
+    /// Put the creation date next to the notes label
+    date_stamp: bool,
+    /// Keep age's ASCII armor in the QR code, see [`pdf::compact`]
+    armor: bool,
+    /// Put the raw ciphertext into the QR code, see [`pdf::Layout::binary_qr`]
+    binary_qr: bool,
+    /// Put a page with recovery instructions before the sheet
+    instructions: bool,
+    /// Name and content of the font picked for the title and notes label
+    font: Option<(ArcStr, pdf::Font)>,
+    /// Why the last picked file couldn't be used as a font
+    font_warning: Option<ArcStr>,
+    /// Read the generated PDF back and decrypt it, see [`decrypt::verify`]
+    verify: bool,
+    /// Show a saved PDF in the default viewer, see [`Message::OpenSaved`]
+    open_after_save: bool,
+    /// Generate right after [`Message::EncryptClipboard`] instead of leaving
+    /// the secret in the editor to review
+    clipboard_generate: bool,
+    /// Show a tray icon from the next launch on, see [`Preferences::tray`]
+    tray: bool,
+    /// Strip whitespace around the editor text before encrypting
+    trim_secret: bool,
+    /// Convert CRLF line endings to LF in text secrets before encrypting
+    normalize_newlines: bool,
+    /// How the editor text is converted to bytes
+    encoding: TextEncoding,
+    /// How several secret files are combined into one
+    archive_format: ArchiveFormat,
+    /// The last generated PDF, kept to save more copies without regenerating
+    last_pdf: Option<ArcBytes>,
+    /// Fingerprint of the ciphertext in `last_pdf`
+    fingerprint: Option<ArcStr>,
+    /// Reuse `last_payload` for identical inputs, so the QR code can be compared
+    reuse_payload: bool,
+    /// Aborts the running generation, see [`App::start_generating`]
+    generate_task: Option<iced::task::Handle>,
+    /// Open the save dialog as soon as the PDF being generated is ready
+    save_when_ready: bool,
+    /// Titles of generated sheets, most recent first
+    title_history: Vec<String>,
+    /// Notes labels of generated sheets, most recent first
+    notes_history: Vec<String>,
+    /// Saved profiles, the disk copy wins when they are changed, see [`App::save_preferences`]
+    profiles: Vec<Profile>,
+    /// Profile last selected or saved
+    profile: Option<ArcStr>,
+    /// Name for saving the current fields as a profile
+    profile_name: ArcStr,
+    /// Set by the Check button when nothing would stop generating
+    ready_to_generate: bool,
+    /// Ciphertext of the last generated PDF, dropped as soon as the inputs change
+    last_payload: Option<ArcStr>,
+    /// Counts changes of the QR code inputs, to tell if a payload is outdated
+    qr_revision: u64,
+    preview_image: Option<image::Handle>,
+    qr_thumbnail: Option<image::Handle>,
+    /// Ciphertext for the current inputs, encrypted once in the background for
+    /// both the QR thumbnail and the exact capacity gauge. Never used for the
+    /// PDF, whose payload is encrypted afresh.
+    ciphertext: Option<ArcStr>,
+    /// When `ciphertext` is due to be recomputed after the inputs changed
+    ciphertext_due: Option<Instant>,
+    /// When the secret field is due to be checked after an edit
+    secret_check_due: Option<Instant>,
+    /// When the passphrase or recipients field is due to be checked after an edit
+    key_check_due: Option<Instant>,
+    last_saved_path: Option<ArcStr>,
+    /// Folder the file dialogs open in, where the user last saved to
+    last_dir: Option<PathBuf>,
+    /// Window waiting for the user to confirm closing with unsaved secrets
+    close_request: Option<window::Id>,
+    /// Current width of the window, which picks the layout
+    window_width: f32,
+    /// Text above the form, `None` hides it and an empty one uses the default
+    heading: Option<ArcStr>,
+    theme: AppTheme,
+    system_theme: theme::Mode,
+    text_scale: f32,
+    idle_timeout: u64,
+    last_interaction: Instant,
+    idle_remaining: Option<Duration>,
+    idle_cleared: bool,
+    /// Name and content of the opened PDFs, several for a split secret
+    decrypt_pdfs: Vec<(ArcStr, ArcBytes)>,
+    decrypt_passphrase: SecretString,
+    decrypt_result: Option<SecretBytes>,
+    decrypt_warning: Option<ArcStr>,
+    is_decrypting: bool,
 }
 
 impl Default for App {
     fn default() -> Self {
         Self {
+            mode: Default::default(),
             title: Default::default(),
             passphrase: Default::default(),
+            passphrase_confirm: Default::default(),
+            passphrase_score: Default::default(),
+            passphrase_file: Default::default(),
+            show_passphrase: Default::default(),
+            passphrase_words: 6,
+            passphrase_copied: Default::default(),
+            toast: Default::default(),
+            use_recipients: Default::default(),
+            recipients: Default::default(),
             secret_content: Default::default(),
-            secret_file_name: Default::default(),
-            secret_file_content: Default::default(),
+            secret_undo: Default::default(),
+            secret_redo: Default::default(),
+            secret_typing: Default::default(),
+            secret_file_names: Default::default(),
+            secret_file_contents: Default::default(),
             secret_file_loading: Default::default(),
+            secret_file_hovered: Default::default(),
+            #[cfg(feature = "url")]
+            secret_url: Default::default(),
+            #[cfg(feature = "url")]
+            secret_url_loading: Default::default(),
             is_file_secret: Default::default(),
+            batch: Default::default(),
+            separate_files: Default::default(),
+            batch_outcomes: Default::default(),
+            secret_size: Default::default(),
+            secret_chars: Default::default(),
+            secret_bytes: Default::default(),
             notes_label: Default::default(),
+            qr_caption: Default::default(),
+            author: Default::default(),
+            subject: Default::default(),
+            notes_warning: Default::default(),
+            qr_caption_warning: Default::default(),
+            output_name: Default::default(),
             show_extra: Default::default(),
+            show_help: Default::default(),
+            show_about: Default::default(),
             secret_warning: Default::default(),
+            confirm_double_encrypt: Default::default(),
+            confirm_weak_passphrase: Default::default(),
             passphrase_warning: Default::default(),
             generate_warning: Default::default(),
+            generate_error_details: Default::default(),
             is_generating: Default::default(),
+            deferred: Default::default(),
+            generate_progress: Default::default(),
+            spinner_rotation: Default::default(),
             page_size: PageSize::A4,
+            grid: Default::default(),
+            ecc_level: Default::default(),
+            print_plaintext: true,
+            date_stamp: Default::default(),
+            armor: true,
+            binary_qr: false,
+            instructions: Default::default(),
+            font: Default::default(),
+            font_warning: Default::default(),
+            verify: true,
+            open_after_save: false,
+            clipboard_generate: true,
+            tray: false,
+            trim_secret: true,
+            normalize_newlines: Default::default(),
+            encoding: Default::default(),
+            archive_format: Default::default(),
+            last_pdf: Default::default(),
+            fingerprint: Default::default(),
+            reuse_payload: Default::default(),
+            save_when_ready: Default::default(),
+            ready_to_generate: Default::default(),
+            generate_task: Default::default(),
+            title_history: Default::default(),
+            notes_history: Default::default(),
+            profiles: Default::default(),
+            profile: Default::default(),
+            profile_name: Default::default(),
+            last_payload: Default::default(),
+            qr_revision: Default::default(),
+            preview_image: Default::default(),
+            qr_thumbnail: Default::default(),
+            ciphertext: Default::default(),
+            ciphertext_due: Default::default(),
+            secret_check_due: Default::default(),
+            key_check_due: Default::default(),
+            last_saved_path: Default::default(),
+            last_dir: Default::default(),
+            close_request: Default::default(),
+            window_width: window::Settings::default().size.width,
+            heading: Some(ArcStr::new()),
+            theme: AppTheme::Fixed(Theme::CatppuccinMocha),
+            system_theme: Default::default(),
+            text_scale: 1.0,
+            idle_timeout: 5,
+            last_interaction: Instant::now(),
+            idle_remaining: Default::default(),
+            idle_cleared: Default::default(),
+            decrypt_pdfs: Default::default(),
+            decrypt_passphrase: Default::default(),
+            decrypt_result: Default::default(),
+            decrypt_warning: Default::default(),
+            is_decrypting: Default::default(),
+        }
+    }
+}
+
+impl App {
+    fn new() -> (Self, Task<Message>) {
+        let preferences = Preferences::load();
+        i18n::set_language(preferences.language);
+        let app = Self {
+            title: preferences.title.into(),
+            notes_label: truncate(&preferences.notes_label, NOTES_LABEL_MAX_LEN).into(),
+            page_size: preferences.page_size,
+            heading: preferences.show_heading.then(|| preferences.heading.into()),
+            theme: preferences.theme,
+            text_scale: preferences
+                .text_scale
+                .clamp(*TEXT_SCALE_RANGE.start(), *TEXT_SCALE_RANGE.end()),
+            idle_timeout: preferences.idle_timeout,
+            last_dir: preferences.last_dir,
+            date_stamp: preferences.date_stamp,
+            armor: preferences.armor,
+            binary_qr: preferences.binary_qr,
+            instructions: preferences.instructions,
+            verify: preferences.verify,
+            open_after_save: preferences.open_after_save,
+            clipboard_generate: preferences.clipboard_generate,
+            tray: preferences.tray,
+            author: preferences.author.into(),
+            trim_secret: preferences.trim_secret,
+            normalize_newlines: preferences.normalize_newlines,
+            archive_format: preferences.archive_format,
+            // Files are never remembered, so file mode starts with an empty picker
+            is_file_secret: preferences.file_secret,
+            title_history: preferences.title_history,
+            notes_history: preferences.notes_history,
+            profiles: preferences.profiles,
+            ..Default::default()
+        };
+        let focus = app.focus_first_field();
+        (
+            app,
+            Task::batch([
+                iced::system::theme().map(Message::SystemThemeChanged),
+                focus,
+            ]),
+        )
+    }
+
+    /// Focus the field the user most likely wants to type into first
+    fn focus_first_field(&self) -> Task<Message> {
+        operation::focus(if !self.is_file_secret {
+            SECRET_EDITOR_ID
+        } else if self.use_recipients {
+            RECIPIENTS_ID
+        } else {
+            PASSPHRASE_ID
+        })
+    }
+
+    fn update_secret_size(&mut self) {
+        self.secret_size = if self.is_file_secret && self.separate_files {
+            // Every file gets its own QR code, so only the largest one has to fit
+            self.secret_file_contents
+                .iter()
+                .map(|content| content.len())
+                .max()
+                .unwrap_or(0)
+        } else if self.is_file_secret {
+            match self.secret_file_contents.as_slice() {
+                [] => 0,
+                [content] => content.len(),
+                contents => self.archive_format.size(
+                    self.secret_file_names
+                        .iter()
+                        .map(|name| name.len())
+                        .zip(contents.iter().map(|content| content.len())),
+                ),
+            }
+        } else if self.batch {
+            // Every record gets its own QR code, so only the largest one has to fit
+            batch::parse(&self.editor_text())
+                .iter()
+                .map(|record| self.encoded_len(&record.secret))
+                .max()
+                .unwrap_or(0)
+        } else {
+            self.encoded_len(&self.secret_text())
+        };
+        self.confirm_double_encrypt = false;
+        self.update_secret_warning();
+    }
+
+    /// Warn about the first problem with the secret, also once the exact
+    /// ciphertext size is known
+    fn update_secret_warning(&mut self) {
+        let usage = self.usage(&self.page_size);
+        self.secret_warning = if let Some(warning) = self.capacity_warning() {
+            Some(warning)
+        } else if let Some(Err(c)) =
+            (!self.is_file_secret).then(|| self.encoding.encode(&self.secret_text()))
+        {
+            Some(App::unencodable_warning(self.encoding, c))
+        } else if !self.is_file_secret && looks_binary(&self.editor_text()) {
+            Some(tr("warning-secret-binary").into())
+        } else if self.secret_is_encrypted() {
+            Some(tr("warning-secret-encrypted").into())
+        } else if usage > capacity::WARNING_THRESHOLD {
+            Some(tr("warning-secret-near-capacity").into())
+        } else {
+            None
+        };
+    }
+
+    /// Warning for a secret that doesn't fit in the QR code, naming a page
+    /// size it fits on if there is one
+    fn capacity_warning(&self) -> Option<ArcStr> {
+        if self.usage(&self.page_size) <= 1.0 {
+            return None;
+        }
+        let fitting_page = PAGE_SIZES
+            .into_iter()
+            .find(|page_size| self.usage(page_size) <= 1.0);
+        Some(match fitting_page {
+            Some(page_size) => tr_args(
+                "warning-secret-too-large-for-page",
+                &fluent_args![
+                    "page" => PageSizeOption(self.page_size.clone()).name(),
+                    "fitting" => PageSizeOption(page_size).name(),
+                ],
+            )
+            .into(),
+            None => tr("warning-secret-too-large").into(),
+        })
+    }
+
+    /// Size of the payload in the QR code, exact once the ciphertext for the
+    /// current inputs is known and estimated until then
+    fn payload_len(&self) -> usize {
+        self.ciphertext.as_ref().map_or_else(
+            || capacity::payload_size(self.secret_size, self.armor, self.binary_qr),
+            |ciphertext| {
+                if self.binary_qr {
+                    capacity::binary_len(ciphertext)
+                } else {
+                    ciphertext.len()
+                }
+            },
+        )
+    }
+
+    /// Fraction of the QR capacity on `page_size` used by the payload
+    fn usage(&self, page_size: &PageSize) -> f32 {
+        capacity::usage(self.payload_len(), page_size, self.ecc_level)
+    }
+
+    /// Size of `text` in the selected encoding, counting unrepresentable
+    /// characters as UTF-8 so the estimate doesn't jump while they are flagged
+    fn encoded_len(&self, text: &str) -> usize {
+        self.encoding
+            .encode(text)
+            .map_or(text.len(), |bytes| bytes.len())
+    }
+
+    fn unencodable_warning(encoding: TextEncoding, c: char) -> ArcStr {
+        tr_args(
+            "warning-encoding-unsupported",
+            &fluent_args!["encoding" => encoding.to_string(), "char" => c.to_string()],
+        )
+        .into()
+    }
+
+    /// Flag an emptied secret field right away instead of on Generate. Other
+    /// warnings about the text are kept up to date by [`App::update_secret_size`].
+    fn check_secret(&mut self) {
+        if !self.is_file_secret && self.secret_text().is_empty() {
+            self.secret_warning = Some(tr("warning-secret-empty").into());
+        }
+    }
+
+    /// Check the passphrase or recipients field while editing. Only an empty
+    /// field raises a new warning, half-typed input is flagged on Generate, but a
+    /// warning already shown follows the field and goes away once it is valid.
+    fn check_key(&mut self) {
+        let (is_empty, result) = match self.key_input() {
+            KeyInput::Passphrase(source) => (
+                source.is_empty(),
+                App::validate_passphrase(&*source).map(drop),
+            ),
+            KeyInput::Recipients(recipients) => (
+                recipients.trim().is_empty(),
+                App::validate_recipients(&recipients).map(drop),
+            ),
+        };
+        self.passphrase_warning = match result {
+            Err(Message::PassphraseWarning(warning))
+                if is_empty || self.passphrase_warning.is_some() =>
+            {
+                Some(warning)
+            }
+            _ => None,
+        };
+    }
+
+    /// Whether both required fields are filled in. Everything else is only
+    /// checked when generating, where the warnings can point at the cause.
+    fn can_generate(&self) -> bool {
+        let has_secret = if self.is_file_secret {
+            !self.secret_file_contents.is_empty()
+        } else {
+            !self.secret_text().is_empty()
+        };
+        let has_key = if self.use_recipients {
+            !self.recipients.trim().is_empty()
+        } else {
+            !self.passphrase.expose_secret().is_empty()
+        };
+        has_secret && has_key
+    }
+
+    /// Secret content that would be lost on exit because nothing was saved yet
+    fn has_unsaved_secret(&self) -> bool {
+        let has_content =
+            !self.editor_text().trim().is_empty() || !self.secret_file_contents.is_empty();
+        has_content && self.last_saved_path.is_none()
+    }
+
+    fn secret(&self) -> Secret {
+        if self.is_file_secret {
+            Secret::Files(
+                self.secret_file_names
+                    .iter()
+                    .cloned()
+                    .zip(self.secret_file_contents.iter().map(|content| {
+                        if self.normalize_newlines {
+                            normalize_newlines(content)
+                        } else {
+                            content.clone()
+                        }
+                    }))
+                    .collect(),
+                self.archive_format,
+            )
+        } else {
+            Secret::Text(self.secret_text(), self.encoding)
+        }
+    }
+
+    /// Everything in the editor, wiped once the caller is done with it
+    fn editor_text(&self) -> Zeroizing<String> {
+        Zeroizing::new(self.secret_content.text())
+    }
+
+    /// Whether generating makes one sheet per batch record or per file
+    fn splits_sheets(&self) -> bool {
+        if self.is_file_secret {
+            self.separate_files
+        } else {
+            self.batch
+        }
+    }
+
+    /// Format the secret files are combined in, if there are several on one sheet
+    fn archive(&self) -> Option<ArchiveFormat> {
+        (self.is_file_secret && !self.separate_files && self.secret_file_names.len() > 1)
+            .then_some(self.archive_format)
+    }
+
+    /// Title and content of every sheet when the secret is split, see [`App::splits_sheets`]
+    fn sheets(&self) -> Vec<(Option<String>, SecretBytes)> {
+        if self.is_file_secret {
+            let Secret::Files(files, _) = self.secret() else {
+                return vec![];
+            };
+            files
+                .into_iter()
+                .map(|(name, content)| {
+                    let title = if self.title.is_empty() {
+                        name.to_string()
+                    } else {
+                        format!("{} - {name}", self.title)
+                    };
+                    (Some(title), content)
+                })
+                .collect()
+        } else {
+            batch::parse(&self.editor_text())
+                .into_iter()
+                .map(|record| {
+                    (
+                        record.title,
+                        // The whole text was validated in the same encoding before
+                        Arc::new(self.encoding.encode(&record.secret).unwrap_or_default()),
+                    )
+                })
+                .collect()
+        }
+    }
+
+    /// Editor text as it will be encrypted
+    fn secret_text(&self) -> Zeroizing<String> {
+        let text = self.editor_text();
+        let text = if self.trim_secret { text.trim() } else { &text };
+        Zeroizing::new(if self.normalize_newlines {
+            text.replace("\r\n", "\n")
+        } else {
+            text.to_string()
+        })
+    }
+
+    fn layout(&self) -> pdf::Layout {
+        pdf::Layout {
+            title: self.title.to_string(),
+            notes_label: self.notes_label.to_string(),
+            qr_caption: self.qr_caption.to_string(),
+            page_size: self.page_size.clone(),
+            grid: self.grid,
+            min_ecc: self.ecc_level,
+            print_armor: self.print_plaintext,
+            armor: self.armor,
+            binary_qr: self.binary_qr,
+            date_stamp: self.date_stamp,
+            font: self.font.as_ref().map(|(_, font)| font.clone()),
+            instructions: self.instructions.then(|| {
+                instructions(
+                    &self.notes_label,
+                    self.armor,
+                    self.binary_qr,
+                    self.archive(),
+                )
+            }),
+            author: self.author.to_string(),
+            subject: self.subject.to_string(),
+        }
+    }
+
+    fn key_input(&self) -> KeyInput {
+        if self.use_recipients {
+            KeyInput::Recipients(self.recipients.clone())
+        } else {
+            KeyInput::Passphrase(Arc::new(Manual {
+                passphrase: self.passphrase.clone(),
+                // The confirmation only applies while the passphrase is hidden
+                confirm: (!self.show_passphrase).then(|| self.passphrase_confirm.clone()),
+            }))
+        }
+    }
+
+    /// Run one of the encrypting jobs unless another one is still in progress,
+    /// showing `toast` once it finishes without warnings
+    fn start_generating(
+        &mut self,
+        generate: Task<Vec<Message>>,
+        toast: Option<ArcStr>,
+    ) -> Task<Message> {
+        if self.is_generating {
+            return Task::none();
+        }
+        // The first attempt only points out the double encryption, the second one proceeds
+        if self.secret_is_encrypted() && !self.confirm_double_encrypt {
+            self.confirm_double_encrypt = true;
+            self.save_when_ready = false;
+            self.secret_warning = Some(tr("warning-secret-encrypted-confirm").into());
+            return Task::none();
+        }
+        // Same for a weak passphrase, an empty one is left to the validation
+        let weakness = (!self.use_recipients && !self.passphrase.expose_secret().is_empty())
+            .then(|| passphrase::weakness(self.passphrase.expose_secret()))
+            .flatten();
+        if let Some(weakness) = weakness.filter(|_| !self.confirm_weak_passphrase) {
+            self.confirm_weak_passphrase = true;
+            self.save_when_ready = false;
+            self.passphrase_warning = Some(
+                tr(match weakness {
+                    Weakness::Short => "warning-passphrase-short",
+                    Weakness::Uniform => "warning-passphrase-uniform",
+                })
+                .into(),
+            );
+            return Task::none();
+        }
+        self.is_generating = true;
+        let (task, handle) = Task::done(Message::ResetWarning)
+            .chain(
+                generate
+                    .then(|v| Task::batch(v.into_iter().map(Task::done)))
+                    .chain(Task::done(Message::GenerateDone(toast))),
+            )
+            .abortable();
+        self.generate_task = Some(handle);
+        task
+    }
+
+    /// Replace the editor content with pasted text
+    fn paste_secret(&mut self, content: &str) {
+        self.remember_secret();
+        // A trailing newline would silently change the ciphertext
+        self.secret_content =
+            text_editor::Content::with_text(content.trim_end_matches(['\r', '\n']));
+        self.update_secret_counter();
+    }
+
+    /// Save the editor content for undo before it is changed
+    fn remember_secret(&mut self) {
+        if self.secret_undo.len() == UNDO_LIMIT {
+            self.secret_undo.remove(0);
+        }
+        self.secret_undo.push(self.editor_text());
+        self.secret_redo.clear();
+    }
+
+    fn restore_secret(&mut self, content: &str) {
+        self.secret_content = text_editor::Content::with_text(content);
+        self.secret_typing = false;
+        self.update_secret_counter();
+    }
+
+    fn update_secret_counter(&mut self) {
+        let content = self.editor_text();
+        self.secret_chars = content.chars().count();
+        self.secret_bytes = content.len();
+        self.update_secret_size();
+    }
+
+    /// Whether the secret is already an age file, so encrypting it again is
+    /// most likely a mistake
+    fn secret_is_encrypted(&self) -> bool {
+        if self.is_file_secret {
+            self.secret_file_contents
+                .iter()
+                .any(|content| is_age_file(content))
+        } else {
+            is_age_file(self.editor_text().trim_start().as_bytes())
+        }
+    }
+
+    /// Offer the title and notes label of a generated sheet again later
+    fn remember_inputs(&mut self) {
+        let remember = |history: &mut Vec<String>, value: &str| {
+            if value.is_empty() {
+                return;
+            }
+            history.retain(|entry| entry != value);
+            history.insert(0, value.to_string());
+            history.truncate(HISTORY_LIMIT);
+        };
+        remember(&mut self.title_history, &self.title);
+        remember(&mut self.notes_history, &self.notes_label);
+        self.save_preferences();
+    }
+
+    /// Change the profiles on disk and show the result
+    fn update_profiles(&mut self, f: impl FnOnce(&mut Vec<Profile>)) {
+        let mut preferences = Preferences::load();
+        f(&mut preferences.profiles);
+        let _ = preferences.save();
+        self.profiles = preferences.profiles;
+    }
+
+    /// Replay the messages held back while generating
+    fn apply_deferred(&mut self) -> Task<Message> {
+        Task::batch(
+            std::mem::take(&mut self.deferred)
+                .into_iter()
+                .map(Task::done),
+        )
+    }
+
+    fn save_preferences(&self) {
+        // Preferences are a convenience, failing to store them must not block the user
+        let _ = Preferences {
+            title: self.title.to_string(),
+            notes_label: self.notes_label.to_string(),
+            page_size: self.page_size.clone(),
+            show_heading: self.heading.is_some(),
+            heading: self
+                .heading
+                .as_ref()
+                .map(ArcStr::to_string)
+                .unwrap_or_default(),
+            theme: self.theme.clone(),
+            text_scale: self.text_scale,
+            language: i18n::language(),
+            idle_timeout: self.idle_timeout,
+            last_dir: self.last_dir.clone(),
+            date_stamp: self.date_stamp,
+            armor: self.armor,
+            binary_qr: self.binary_qr,
+            instructions: self.instructions,
+            verify: self.verify,
+            open_after_save: self.open_after_save,
+            clipboard_generate: self.clipboard_generate,
+            tray: self.tray,
+            trim_secret: self.trim_secret,
+            normalize_newlines: self.normalize_newlines,
+            archive_format: self.archive_format,
+            file_secret: self.is_file_secret,
+            author: self.author.to_string(),
+            title_history: self.title_history.clone(),
+            notes_history: self.notes_history.clone(),
+            // Another window may have changed the profiles since this one loaded them
+            profiles: Preferences::load().profiles,
         }
+        .save();
+    }
+
+    /// Text size adjusted by the user's text scale
+    fn scaled(&self, base: f32) -> f32 {
+        base * self.text_scale
+    }
+
+    /// Whether there is anything worth clearing after a period of inactivity
+    fn has_secrets(&self) -> bool {
+        !self.passphrase.expose_secret().is_empty()
+            || !self.passphrase_confirm.expose_secret().is_empty()
+            || !self.editor_text().trim().is_empty()
+            || !self.secret_file_contents.is_empty()
+            || !self.decrypt_passphrase.expose_secret().is_empty()
+            || self.decrypt_result.is_some()
     }
 }
 
 #[derive(Debug, Clone)]
 pub enum Message {
+    SwitchMode(AppMode),
     TitleChanged(String),
     PassphraseChanged(String),
+    PassphraseConfirmChanged(String),
+    TogglePassphraseVisibility(bool),
+    GeneratePassphrase,
+    PassphraseWordsChanged(usize),
+    CopyPassphrase,
+    PassphraseFilePick,
+    PassphraseFileLoad(Option<FileHandle>),
+    PassphraseFileRead(Result<(ArcStr, SecretString), ArcStr>),
+    ToggleRecipients(bool),
+    RecipientsChanged(String),
+    ClearCopyNotice,
+    ClearToast,
     SecretContentChanged(text_editor::Action),
-    SecretFileChanged(ArcBytes),
-    SecretFileLoad(Option<FileHandle>),
+    SecretPaste,
+    SecretUndo,
+    SecretRedo,
+    SecretPasted(Option<String>),
+    EncryptClipboard,
+    ClipboardRead(Option<String>),
+    SecretFileChanged(ArcStr, SecretBytes),
+    SecretFileLoad(Option<Vec<FileHandle>>),
     SecretFilePick,
+    SecretFileRemove(usize),
+    #[cfg(feature = "url")]
+    SecretUrlChanged(String),
+    #[cfg(feature = "url")]
+    SecretUrlLoad,
+    #[cfg(feature = "url")]
+    SecretUrlLoaded(Result<(ArcStr, SecretBytes), ArcStr>),
+    FileHovered(bool),
+    FileDropped(PathBuf),
     PageSizeChanged(PageSize),
+    ToggleGrid(bool),
+    EccLevelChanged(EccLevel),
+    TogglePrintPlaintext(bool),
+    ToggleDateStamp(bool),
+    ToggleArmor(bool),
+    ToggleBinaryQr(bool),
+    ToggleInstructions(bool),
+    ToggleVerify(bool),
+    ToggleOpenAfterSave(bool),
+    ToggleClipboardGenerate(bool),
+    #[cfg(feature = "tray")]
+    ToggleTray(bool),
+    ToggleTrimSecret(bool),
+    ToggleNormalizeNewlines(bool),
+    EncodingChanged(TextEncoding),
+    ArchiveFormatChanged(ArchiveFormat),
+    ThemeChanged(AppTheme),
+    ToggleHeading(bool),
+    HeadingChanged(String),
+    SystemThemeChanged(theme::Mode),
+    TextScaleChanged(f32),
+    LanguageChanged(Language),
+    IdleTimeoutChanged(u64),
+    IdleTick,
     NotesLabelChanged(String),
+    OutputNameChanged(String),
     ToggleExtraSpoiler,
+    ToggleHelp,
+    ToggleAbout,
+    OpenRepository,
     GeneratePdf,
+    GenerateAndSave,
+    Validate,
+    ProfileSelected(ArcStr),
+    ClearInputHistory,
+    ProfileNameChanged(String),
+    SaveProfile,
+    DeleteProfile,
+    PreviewReady(ArcBytes),
+    FingerprintReady(ArcStr),
+    PayloadReady(u64, ArcStr),
+    ToggleReusePayload(bool),
+    PreviewRendered(Option<image::Handle>),
     SaveSecretPdf(ArcBytes),
-    GenerateDone,
+    /// Show the PDF just saved to the given path in the default viewer. The
+    /// content is shown instead on the web, where downloads have no path.
+    OpenSaved(ArcStr, ArcBytes),
+    PrintPdf(ArcBytes),
+    SaveDone(Option<ArcStr>),
+    SaveError(ArcStr),
+    OpenSaveFolder,
+    GenerateProgress(f32),
+    GenerateDone(Option<ArcStr>),
+    CancelGenerate,
+    ExportQrPng,
+    SaveQrPng(ArcBytes),
+    ExportAge,
+    SaveAge(ArcStr),
+    Tick,
     SecretWarning(ArcStr),
     GenerateWarning(ArcStr),
+    GenerateErrorDetails(ArcStr),
     PassphraseWarning(ArcStr),
     ToggleSecretSource(bool),
+    ToggleBatch(bool),
+    ToggleSeparateFiles(bool),
+    BatchGenerate,
+    BatchFolderPicked(Option<BatchFolder>),
+    GenerateSplit,
+    SplitFolderPicked(Option<BatchFolder>),
+    BatchDone(Vec<BatchOutcome>),
     ResetWarning,
+    ClearSecrets,
+    /// Clears the form and the previews of the last secret to enter the next one
+    NewSecret,
+    ClearTitle,
+    ClearNotesLabel,
+    QrCaptionChanged(String),
+    ClearQrCaption,
+    AuthorChanged(String),
+    SubjectChanged(String),
+    FontPick,
+    FontLoad(Option<FileHandle>),
+    FontRead(Result<(ArcStr, pdf::Font), ArcStr>),
+    ClearFont,
+    ClearPassphrase,
+    DecryptFilePick,
+    DecryptFileLoad(Option<Vec<FileHandle>>),
+    DecryptFileChanged(Vec<(ArcStr, ArcBytes)>),
+    DecryptPassphraseChanged(String),
+    Decrypt,
+    DecryptDone(Result<SecretBytes, ArcStr>),
+    FocusNext,
+    FocusPrevious,
+    CloseRequested(window::Id),
+    WindowResized(Size),
+    ConfirmClose,
+    CiphertextTick,
+    /// Ciphertext and QR thumbnail for the given `qr_revision`
+    CiphertextReady(u64, Option<(ArcStr, Option<ArcBytes>)>),
+    ValidationTick,
 }
 
 #[cfg(target_arch = "wasm32")]
 unsafe impl Send for Message {}
 
+impl Message {
+    /// Outcome of a save dialog, a cancelled dialog is not an error
+    fn saved(result: Result<Option<ArcStr>>) -> Message {
+        match result {
+            Ok(path) => Message::SaveDone(path),
+            Err(err) => Message::SaveError(err.to_string().into()),
+        }
+    }
+
+    /// Messages that change what the next PDF would contain
+    fn changes_pdf(&self) -> bool {
+        self.changes_qr_code()
+            || matches!(
+                self,
+                Message::TitleChanged(_)
+                    | Message::PageSizeChanged(_)
+                    | Message::ToggleGrid(_)
+                    | Message::TogglePrintPlaintext(_)
+                    | Message::ToggleDateStamp(_)
+                    | Message::ToggleInstructions(_)
+                    | Message::NotesLabelChanged(_)
+                    | Message::QrCaptionChanged(_)
+                    | Message::AuthorChanged(_)
+                    | Message::SubjectChanged(_)
+                    | Message::FontRead(Ok(_))
+                    | Message::ClearFont
+            )
+    }
+
+    /// Messages that change the encrypted payload in the QR code
+    fn changes_qr_code(&self) -> bool {
+        match self {
+            Message::SecretContentChanged(action) => action.is_edit(),
+            Message::PassphraseChanged(_)
+            | Message::PassphraseConfirmChanged(_)
+            | Message::GeneratePassphrase
+            | Message::PassphraseFileRead(_)
+            | Message::ClearPassphrase
+            | Message::ToggleRecipients(_)
+            | Message::RecipientsChanged(_)
+            | Message::SecretPasted(_)
+            | Message::ClipboardRead(_)
+            | Message::SecretUndo
+            | Message::SecretRedo
+            | Message::SecretFileChanged(..)
+            | Message::SecretFileRemove(_)
+            | Message::EccLevelChanged(_)
+            | Message::ToggleArmor(_)
+            | Message::ToggleBinaryQr(_)
+            | Message::ToggleTrimSecret(_)
+            | Message::ToggleNormalizeNewlines(_)
+            | Message::EncodingChanged(_)
+            | Message::ArchiveFormatChanged(_)
+            | Message::ToggleSecretSource(_)
+            | Message::ToggleBatch(_)
+            | Message::ToggleSeparateFiles(_)
+            | Message::ClearSecrets => true,
+            _ => false,
+        }
+    }
+
+    /// Messages that aren't direct edits, but arrive later from a dialog, a
+    /// file read or another message. They wait for generating to finish.
+    fn arrives_later(&self) -> bool {
+        matches!(
+            self,
+            Message::SecretFileChanged(..)
+                | Message::PassphraseFileRead(_)
+                | Message::ClipboardRead(_)
+                | Message::ProfileSelected(_)
+        )
+    }
+
+    /// Edits of the secret text that are checked once typing pauses
+    fn edits_secret(&self) -> bool {
+        match self {
+            Message::SecretContentChanged(action) => action.is_edit(),
+            Message::SecretPasted(_)
+            | Message::ClipboardRead(_)
+            | Message::SecretUndo
+            | Message::SecretRedo => true,
+            _ => false,
+        }
+    }
+
+    /// Edits of the passphrase or recipients that are checked once typing pauses
+    fn edits_key(&self) -> bool {
+        matches!(
+            self,
+            Message::PassphraseChanged(_)
+                | Message::PassphraseConfirmChanged(_)
+                | Message::RecipientsChanged(_)
+        )
+    }
+
+    /// Messages that count as activity for the idle auto-clear
+    fn touches_secrets(&self) -> bool {
+        matches!(
+            self,
+            Message::PassphraseChanged(_)
+                | Message::PassphraseConfirmChanged(_)
+                | Message::TogglePassphraseVisibility(_)
+                | Message::GeneratePassphrase
+                | Message::CopyPassphrase
+                | Message::PassphraseFileRead(_)
+                | Message::SecretContentChanged(_)
+                | Message::SecretPasted(_)
+                | Message::ClipboardRead(_)
+                | Message::SecretUndo
+                | Message::SecretRedo
+                | Message::SecretFileChanged(..)
+                | Message::SecretFileRemove(_)
+                | Message::DecryptPassphraseChanged(_)
+                | Message::DecryptDone(_)
+        )
+    }
+}
+
 impl App {
     fn update(&mut self, event: Message) -> Task<Message> {
+        // The busy overlay only stops the mouse, a focused field or a shortcut
+        // could still change the inputs of the running task. Wiping the secrets
+        // and fonts picked before it started are let through, other results
+        // are applied once it is done.
+        if self.is_generating && event.arrives_later() {
+            self.deferred.push(event);
+            return Task::none();
+        }
+        // The instructions page is the only localized part of the PDF
+        let changes_pdf = event.changes_pdf()
+            || (self.instructions && matches!(event, Message::LanguageChanged(_)));
+        if self.is_generating
+            && changes_pdf
+            && !matches!(event, Message::ClearSecrets | Message::FontRead(_))
+        {
+            return Task::none();
+        }
+        if event.touches_secrets() {
+            self.last_interaction = Instant::now();
+            self.idle_remaining = None;
+            self.idle_cleared = false;
+        }
+        if changes_pdf {
+            self.last_pdf = None;
+            self.fingerprint = None;
+            self.preview_image = None;
+        }
+        if event.changes_qr_code() {
+            self.confirm_weak_passphrase = false;
+            self.qr_revision += 1;
+            self.last_payload = None;
+            self.ciphertext = None;
+        }
+        if changes_pdf || event.changes_qr_code() {
+            self.ready_to_generate = false;
+        }
+        // There are no threads on the web, so encrypting after every pause in
+        // typing would freeze the page
+        if cfg!(not(target_arch = "wasm32")) && event.changes_qr_code() {
+            self.ciphertext_due = Some(Instant::now() + CIPHERTEXT_DELAY);
+        }
+        if event.edits_secret() {
+            self.secret_check_due = Some(Instant::now() + VALIDATION_DELAY);
+        }
+        if event.edits_key() {
+            self.key_check_due = Some(Instant::now() + VALIDATION_DELAY);
+        }
         match event {
-            Message::TitleChanged(data) => {
-                self.title = data.into();
-                Task::none()
-            }
-            Message::PassphraseChanged(data) => {
-                self.passphrase = data.into();
+            Message::SwitchMode(mode) => {
+                self.mode = mode;
                 Task::none()
             }
-            Message::SecretContentChanged(action) => {
-                self.secret_content.perform(action);
-                Task::none()
+            Message::DecryptFilePick => Task::perform(
+                self.file_dialog().add_filter("PDF", &["pdf"]).pick_files(),
+                Message::DecryptFileLoad,
+            ),
+            Message::DecryptFileLoad(handles) => {
+                if let Some(handles) = handles {
+                    Task::perform(
+                        async move {
+                            let mut pdfs = vec![];
+                            for f in handles {
+                                pdfs.push((f.file_name().into(), f.read().await.into()));
+                            }
+                            pdfs
+                        },
+                        Message::DecryptFileChanged,
+                    )
+                } else {
+                    Task::none()
+                }
             }
-            Message::NotesLabelChanged(data) => {
-                self.notes_label = data.into();
+            Message::DecryptFileChanged(pdfs) => {
+                self.decrypt_pdfs = pdfs;
+                self.decrypt_result = None;
+                self.decrypt_warning = None;
                 Task::none()
             }
-            Message::ToggleExtraSpoiler => {
-                self.show_extra = !self.show_extra;
+            Message::DecryptPassphraseChanged(data) => {
+                self.decrypt_passphrase = data.into();
                 Task::none()
             }
-            Message::GeneratePdf => {
-                if self.is_generating {
+            Message::Decrypt => {
+                if self.is_decrypting {
                     return Task::none();
                 }
-                self.is_generating = true;
-                Task::done(Message::ResetWarning).chain(
-                    Task::future(App::generate_pdf(
-                        self.title.clone(),
-                        self.notes_label.clone(),
-                        self.page_size.clone(),
-                        if self.is_file_secret {
-                            self.secret_file_content.clone()
-                        } else {
-                            Some(self.secret_content.text().trim().as_bytes().into())
-                        },
-                        self.passphrase.clone(),
-                    ))
-                    .then(|v| Task::batch(v.into_iter().map(Task::done)))
-                    .chain(Task::done(Message::GenerateDone)),
+                if self.decrypt_pdfs.is_empty() {
+                    self.decrypt_warning = Some(tr("warning-select-pdf").into());
+                    return Task::none();
+                }
+                let pdfs = self
+                    .decrypt_pdfs
+                    .iter()
+                    .map(|(_, pdf)| pdf.clone())
+                    .collect();
+                self.is_decrypting = true;
+                self.decrypt_result = None;
+                self.decrypt_warning = None;
+                Task::perform(
+                    App::decrypt_pdfs(pdfs, self.decrypt_passphrase.clone()),
+                    Message::DecryptDone,
                 )
             }
-            Message::SaveSecretPdf(content) => {
-                Task::perform(Self::save_pdf(content), |x| x).then(|_| Task::none())
-            }
-            Message::GenerateDone => {
-                self.is_generating = false;
+            Message::DecryptDone(result) => {
+                self.is_decrypting = false;
+                match result {
+                    Ok(plaintext) => self.decrypt_result = Some(plaintext),
+                    Err(warning) => self.decrypt_warning = Some(warning),
+                }
                 Task::none()
             }
-            Message::SecretWarning(warning) => {
-                self.secret_warning = Some(warning);
+            Message::TitleChanged(data) => {
+                self.title = data.into();
+                self.save_preferences();
                 Task::none()
             }
-            Message::PassphraseWarning(warning) => {
-                self.passphrase_warning = Some(warning);
+            Message::PassphraseChanged(data) => {
+                self.passphrase_score = if data.is_empty() {
+                    None
+                } else {
+                    Some(zxcvbn::zxcvbn(&data, &[self.title.as_str()]).score().into())
+                };
+                self.passphrase = data.into();
+                self.passphrase_file = None;
                 Task::none()
             }
-            Message::ResetWarning => {
-                self.passphrase_warning = None;
-                self.secret_warning = None;
-                self.generate_warning = None;
-                Task::none()
+            Message::PassphraseFilePick => {
+                Task::perform(self.file_dialog().pick_file(), Message::PassphraseFileLoad)
+            }
+            Message::PassphraseFileLoad(handle) => match handle {
+                Some(file) => {
+                    Task::perform(App::read_passphrase_file(file), Message::PassphraseFileRead)
+                }
+                None => Task::none(),
+            },
+            Message::PassphraseFileRead(result) => {
+                match result {
+                    Ok((name, passphrase)) => {
+                        self.passphrase_score = Some(
+                            zxcvbn::zxcvbn(passphrase.expose_secret(), &[self.title.as_str()])
+                                .score()
+                                .into(),
+                        );
+                        // The file is the source of truth, there is nothing to retype
+                        self.passphrase_confirm = passphrase.clone();
+                        self.passphrase = passphrase;
+                        self.passphrase_file = Some(name);
+                        self.passphrase_warning = None;
+                    }
+                    Err(warning) => self.passphrase_warning = Some(warning),
+                }
+                Task::none()
+            }
+            Message::PassphraseConfirmChanged(data) => {
+                self.passphrase_confirm = data.into();
+                Task::none()
+            }
+            Message::TogglePassphraseVisibility(b) => {
+                self.show_passphrase = b;
+                Task::none()
+            }
+            Message::GeneratePassphrase => {
+                let content = String::from_utf8_lossy(Assets.eff_large_wordlist().content());
+                let words: Vec<&str> = content.lines().collect();
+                let passphrase = (0..self.passphrase_words)
+                    .filter_map(|_| words.choose(&mut rand::rngs::OsRng))
+                    .copied()
+                    .collect::<Vec<_>>()
+                    .join("-");
+                self.show_passphrase = true;
+                Task::done(Message::PassphraseChanged(passphrase))
+            }
+            Message::PassphraseWordsChanged(words) => {
+                self.passphrase_words = words;
+                Task::none()
+            }
+            Message::CopyPassphrase => {
+                self.passphrase_copied = true;
+                iced::clipboard::write(self.passphrase.expose_secret().to_string())
+            }
+            Message::ToggleRecipients(b) => {
+                self.use_recipients = b;
+                self.passphrase_warning = None;
+                Task::none()
+            }
+            Message::RecipientsChanged(data) => {
+                self.recipients = data.into();
+                Task::none()
+            }
+            Message::ClearCopyNotice => {
+                self.passphrase_copied = false;
+                Task::none()
+            }
+            Message::ClearToast => {
+                self.toast = None;
+                Task::none()
+            }
+            Message::SecretContentChanged(action) => {
+                match &action {
+                    // Typing a word is undone at once, like in most editors
+                    text_editor::Action::Edit(text_editor::Edit::Insert(c))
+                        if !c.is_whitespace() =>
+                    {
+                        if !self.secret_typing {
+                            self.remember_secret();
+                        }
+                        self.secret_typing = true;
+                    }
+                    text_editor::Action::Edit(_) => {
+                        self.remember_secret();
+                        self.secret_typing = false;
+                    }
+                    _ => self.secret_typing = false,
+                }
+                let is_edit = action.is_edit();
+                self.secret_content.perform(action);
+                if is_edit {
+                    self.update_secret_counter();
+                }
+                Task::none()
+            }
+            Message::SecretUndo => {
+                if let Some(previous) = self.secret_undo.pop() {
+                    self.secret_redo.push(self.editor_text());
+                    self.restore_secret(&previous);
+                }
+                Task::none()
+            }
+            Message::SecretRedo => {
+                if let Some(next) = self.secret_redo.pop() {
+                    self.secret_undo.push(self.editor_text());
+                    self.restore_secret(&next);
+                }
+                Task::none()
+            }
+            Message::SecretPaste => iced::clipboard::read().map(Message::SecretPasted),
+            Message::SecretPasted(content) => {
+                if let Some(content) = content {
+                    self.paste_secret(&content);
+                }
+                Task::none()
+            }
+            Message::EncryptClipboard => iced::clipboard::read().map(Message::ClipboardRead),
+            Message::ClipboardRead(content) => match content.filter(|c| !c.trim().is_empty()) {
+                Some(content) => {
+                    self.is_file_secret = false;
+                    self.paste_secret(&content);
+                    self.update_secret_size();
+                    if self.clipboard_generate {
+                        Task::done(Message::GeneratePdf)
+                    } else {
+                        Task::none()
+                    }
+                }
+                // Report an empty clipboard like an empty secret, without touching the editor
+                None => match App::validate_secret(Secret::Text(
+                    Default::default(),
+                    TextEncoding::default(),
+                )) {
+                    Err(warning) => Task::done(warning),
+                    Ok(_) => Task::none(),
+                },
+            },
+            Message::NotesLabelChanged(data) => {
+                let label = truncate(&data, NOTES_LABEL_MAX_LEN);
+                self.notes_warning = (label.len() < data.len()).then(|| {
+                    tr_args(
+                        "warning-notes-too-long",
+                        &fluent_args!["max" => NOTES_LABEL_MAX_LEN],
+                    )
+                    .into()
+                });
+                self.notes_label = label.into();
+                self.save_preferences();
+                Task::none()
+            }
+            Message::OutputNameChanged(data) => {
+                self.output_name = (!data.is_empty()).then(|| data.into());
+                Task::none()
+            }
+            Message::ToggleExtraSpoiler => {
+                self.show_extra = !self.show_extra;
+                Task::none()
+            }
+            Message::ToggleHelp => {
+                self.show_help = !self.show_help;
+                Task::none()
+            }
+            Message::GeneratePdf if self.splits_sheets() => Task::done(Message::BatchGenerate),
+            Message::GeneratePdf => {
+                let payload = self.last_payload.clone().filter(|_| self.reuse_payload);
+                let generate = App::generate_pdf(
+                    self.layout(),
+                    self.secret(),
+                    self.key_input(),
+                    payload,
+                    self.verify,
+                    self.qr_revision,
+                );
+                self.start_generating(
+                    Task::run(generate, |message| vec![message]),
+                    Some(tr("toast-pdf-generated").into()),
+                )
+            }
+            Message::Validate => {
+                let mut warnings = App::validate_key(self.secret(), self.key_input())
+                    .err()
+                    .unwrap_or_default();
+                // Only encrypting reveals the exact size, so the estimate stands in
+                // for the check `paper_age` does while laying out the page
+                let secret_valid = !warnings
+                    .iter()
+                    .any(|warning| matches!(warning, Message::SecretWarning(_)));
+                if let Some(warning) = self.capacity_warning().filter(|_| secret_valid) {
+                    warnings.push(Message::SecretWarning(warning));
+                }
+                self.ready_to_generate = warnings.is_empty();
+                Task::done(Message::ResetWarning)
+                    .chain(Task::batch(warnings.into_iter().map(Task::done)))
+            }
+            Message::ProfileSelected(name) => {
+                let Some(profile) = self.profiles.iter().find(|p| *p.name == *name).cloned() else {
+                    return Task::none();
+                };
+                self.profile = Some(name);
+                Task::batch(
+                    [
+                        Message::TitleChanged(profile.title),
+                        Message::NotesLabelChanged(profile.notes_label),
+                        Message::QrCaptionChanged(profile.qr_caption),
+                        Message::PageSizeChanged(profile.page_size),
+                        Message::EncodingChanged(profile.encoding),
+                    ]
+                    .map(Task::done),
+                )
+            }
+            Message::ClearInputHistory => {
+                self.title_history.clear();
+                self.notes_history.clear();
+                self.save_preferences();
+                Task::none()
+            }
+            Message::ProfileNameChanged(name) => {
+                self.profile_name = name.into();
+                Task::none()
+            }
+            Message::SaveProfile => {
+                let name = self.profile_name.trim();
+                if name.is_empty() {
+                    return Task::none();
+                }
+                let profile = Profile {
+                    name: name.to_string(),
+                    title: self.title.to_string(),
+                    notes_label: self.notes_label.to_string(),
+                    qr_caption: self.qr_caption.to_string(),
+                    page_size: self.page_size.clone(),
+                    encoding: self.encoding,
+                };
+                self.profile = Some(name.into());
+                self.profile_name = ArcStr::new();
+                self.update_profiles(|profiles| {
+                    match profiles.iter_mut().find(|p| p.name == profile.name) {
+                        Some(existing) => *existing = profile,
+                        None => profiles.push(profile),
+                    }
+                });
+                Task::none()
+            }
+            Message::DeleteProfile => {
+                if let Some(name) = self.profile.take() {
+                    self.update_profiles(|profiles| profiles.retain(|p| *p.name != *name));
+                }
+                Task::none()
+            }
+            Message::GenerateAndSave => {
+                if self.is_generating {
+                    return Task::none();
+                }
+                if self.splits_sheets() {
+                    // Batches are saved as they are generated
+                    return Task::done(Message::BatchGenerate);
+                }
+                match self.last_pdf.clone() {
+                    // The inputs haven't changed since, so the PDF is still current
+                    Some(pdf) => Task::done(Message::SaveSecretPdf(pdf)),
+                    None => {
+                        self.save_when_ready = true;
+                        Task::done(Message::GeneratePdf)
+                    }
+                }
+            }
+            Message::BatchGenerate => {
+                if self.is_generating {
+                    return Task::none();
+                }
+                Task::perform(
+                    App::pick_batch_folder(self.file_dialog()),
+                    Message::BatchFolderPicked,
+                )
+            }
+            Message::BatchFolderPicked(folder) => {
+                let Some(folder) = folder else {
+                    return Task::none();
+                };
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    self.last_dir = Some(folder.clone());
+                    self.save_preferences();
+                }
+                self.batch_outcomes.clear();
+                let generate = App::generate_batch(
+                    folder,
+                    self.layout(),
+                    self.secret(),
+                    self.sheets(),
+                    self.key_input(),
+                );
+                self.start_generating(Task::future(generate), None)
+            }
+            Message::GenerateSplit => {
+                if self.is_generating {
+                    return Task::none();
+                }
+                Task::perform(
+                    App::pick_batch_folder(self.file_dialog()),
+                    Message::SplitFolderPicked,
+                )
+            }
+            Message::SplitFolderPicked(folder) => {
+                let Some(folder) = folder else {
+                    return Task::none();
+                };
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    self.last_dir = Some(folder.clone());
+                    self.save_preferences();
+                }
+                self.batch_outcomes.clear();
+                let generate =
+                    App::generate_split(folder, self.layout(), self.secret(), self.key_input());
+                self.start_generating(Task::future(generate), None)
+            }
+            Message::BatchDone(outcomes) => {
+                if let Some(path) = outcomes
+                    .iter()
+                    .rev()
+                    .find_map(|outcome| outcome.saved_to.clone().ok())
+                {
+                    self.last_saved_path = Some(path);
+                }
+                self.batch_outcomes = outcomes;
+                Task::none()
+            }
+            Message::GenerateProgress(progress) => {
+                self.generate_progress = Some(progress);
+                Task::none()
+            }
+            Message::ExportQrPng => {
+                let generate = App::generate_qr_png(
+                    self.secret(),
+                    self.key_input(),
+                    self.ecc_level,
+                    self.armor,
+                    self.binary_qr,
+                );
+                self.start_generating(Task::future(generate), None)
+            }
+            Message::SaveQrPng(content) => Task::perform(
+                Self::save_png(self.file_dialog(), content, self.output_file_name("png")),
+                Message::saved,
+            ),
+            Message::ExportAge => {
+                let generate = App::generate_age(self.secret(), self.key_input());
+                self.start_generating(Task::future(generate), None)
+            }
+            Message::SaveAge(content) => Task::perform(
+                Self::save_age(self.file_dialog(), content, self.output_file_name("age")),
+                Message::saved,
+            ),
+            Message::PreviewReady(content) => {
+                self.last_pdf = Some(content.clone());
+                self.remember_inputs();
+                let preview = Task::perform(
+                    App::render_preview(content.clone()),
+                    Message::PreviewRendered,
+                );
+                if std::mem::take(&mut self.save_when_ready) {
+                    Task::batch([preview, Task::done(Message::SaveSecretPdf(content))])
+                } else {
+                    preview
+                }
+            }
+            Message::PayloadReady(revision, payload) => {
+                // The inputs changed while generating, so the payload no longer matches them
+                if revision == self.qr_revision {
+                    self.last_payload = Some(payload);
+                }
+                Task::none()
+            }
+            Message::ToggleReusePayload(reuse_payload) => {
+                self.reuse_payload = reuse_payload;
+                Task::none()
+            }
+            Message::FingerprintReady(fingerprint) => {
+                self.fingerprint = Some(fingerprint);
+                Task::none()
+            }
+            Message::PreviewRendered(handle) => {
+                self.preview_image = handle;
+                Task::none()
+            }
+            Message::SaveSecretPdf(content) => {
+                let open_after_save = self.open_after_save;
+                Task::future(Self::save_pdf(
+                    self.file_dialog(),
+                    content.clone(),
+                    self.output_file_name("pdf"),
+                ))
+                .then(move |result| {
+                    let open = match &result {
+                        Ok(Some(path)) if open_after_save => {
+                            Task::done(Message::OpenSaved(path.clone(), content.clone()))
+                        }
+                        _ => Task::none(),
+                    };
+                    Task::done(Message::saved(result)).chain(open)
+                })
+            }
+            Message::OpenSaved(path, content) => match open_saved(&path, &content) {
+                Ok(()) => Task::none(),
+                Err(err) => Task::batch([
+                    Task::done(Message::GenerateWarning(tr("warning-open-failed").into())),
+                    Task::done(Message::GenerateErrorDetails(err.to_string().into())),
+                ]),
+            },
+            Message::PrintPdf(content) => {
+                let file_name = self.output_file_name("pdf");
+                Task::future(spawn_blocking(move || print(&content, &file_name))).then(|result| {
+                    match result {
+                        Ok(()) => Task::none(),
+                        Err(err) => Task::batch([
+                            Task::done(Message::GenerateWarning(tr("warning-print-failed").into())),
+                            Task::done(Message::GenerateErrorDetails(err.to_string().into())),
+                        ]),
+                    }
+                })
+            }
+            Message::SaveDone(path) => {
+                #[cfg(not(target_arch = "wasm32"))]
+                if let Some(dir) = path
+                    .as_ref()
+                    .and_then(|path| std::path::Path::new(path.as_str()).parent())
+                {
+                    self.last_dir = Some(dir.to_path_buf());
+                    self.save_preferences();
+                }
+                if path.is_some() {
+                    self.last_saved_path = path;
+                }
+                Task::none()
+            }
+            Message::ToggleAbout => {
+                self.show_about = !self.show_about;
+                Task::none()
+            }
+            Message::OpenRepository => {
+                #[cfg(not(target_arch = "wasm32"))]
+                let _ = opener::open_browser(env!("CARGO_PKG_REPOSITORY"));
+                #[cfg(target_arch = "wasm32")]
+                if let Some(window) = web_sys::window() {
+                    let _ = window.open_with_url(env!("CARGO_PKG_REPOSITORY"));
+                }
+                Task::none()
+            }
+            Message::SaveError(error) => {
+                self.generate_warning = Some(tr("warning-save-failed").into());
+                self.generate_error_details = Some(error);
+                Task::none()
+            }
+            Message::OpenSaveFolder => {
+                #[cfg(not(target_arch = "wasm32"))]
+                if let Some(folder) = self
+                    .last_saved_path
+                    .as_ref()
+                    .and_then(|path| std::path::Path::new(path.as_str()).parent())
+                {
+                    let _ = opener::open(folder);
+                }
+                Task::none()
+            }
+            Message::GenerateDone(toast) => {
+                self.generate_task = None;
+                self.is_generating = false;
+                self.save_when_ready = false;
+                self.generate_progress = None;
+                // Warnings were reset when generating started, so any warning now
+                // means something along the way failed
+                let succeeded = self.generate_warning.is_none()
+                    && self.secret_warning.is_none()
+                    && self.passphrase_warning.is_none();
+                if succeeded && toast.is_some() {
+                    self.toast = toast;
+                }
+                self.apply_deferred()
+            }
+            Message::CancelGenerate => {
+                // Encrypting can't be interrupted, so it finishes in the background
+                // and its result is dropped. The plaintext it holds is wiped with
+                // the last copy of the secret bytes.
+                if let Some(handle) = self.generate_task.take() {
+                    handle.abort();
+                }
+                self.is_generating = false;
+                self.save_when_ready = false;
+                self.generate_progress = None;
+                self.apply_deferred()
+            }
+            Message::Tick => {
+                self.spinner_rotation =
+                    (self.spinner_rotation + std::f32::consts::TAU / 60.0) % std::f32::consts::TAU;
+                Task::none()
+            }
+            // The fields are at the top, where the form has to be scrolled back
+            // to for the warning to be seen
+            Message::SecretWarning(warning) => {
+                self.secret_warning = Some(warning);
+                operation::snap_to(CONTENT_ID, RelativeOffset::START)
+            }
+            Message::PassphraseWarning(warning) => {
+                self.passphrase_warning = Some(warning);
+                operation::snap_to(CONTENT_ID, RelativeOffset::START)
+            }
+            Message::ResetWarning => {
+                self.passphrase_warning = None;
+                self.secret_warning = None;
+                self.notes_warning = None;
+                self.qr_caption_warning = None;
+                self.generate_warning = None;
+                self.generate_error_details = None;
+                self.close_request = None;
+                Task::none()
+            }
+            Message::FocusNext => operation::focus_next(),
+            Message::FocusPrevious => operation::focus_previous(),
+            Message::WindowResized(size) => {
+                self.window_width = size.width;
+                Task::none()
+            }
+            Message::CloseRequested(id) => {
+                if self.has_unsaved_secret() {
+                    self.close_request = Some(id);
+                    Task::none()
+                } else {
+                    window::close(id)
+                }
+            }
+            Message::ConfirmClose => match self.close_request.take() {
+                Some(id) => window::close(id),
+                None => Task::none(),
+            },
+            Message::ClearTitle => Task::done(Message::TitleChanged(String::new())),
+            Message::ClearNotesLabel => Task::done(Message::NotesLabelChanged(String::new())),
+            Message::QrCaptionChanged(data) => {
+                let caption = truncate(&data, NOTES_LABEL_MAX_LEN);
+                self.qr_caption_warning = (caption.len() < data.len()).then(|| {
+                    tr_args(
+                        "warning-caption-too-long",
+                        &fluent_args!["max" => NOTES_LABEL_MAX_LEN],
+                    )
+                    .into()
+                });
+                self.qr_caption = caption.into();
+                Task::none()
+            }
+            Message::ClearQrCaption => Task::done(Message::QrCaptionChanged(String::new())),
+            Message::AuthorChanged(author) => {
+                self.author = author.into();
+                self.save_preferences();
+                Task::none()
+            }
+            Message::SubjectChanged(subject) => {
+                self.subject = subject.into();
+                Task::none()
+            }
+            Message::FontPick => Task::perform(
+                self.file_dialog()
+                    .add_filter("Fonts", &["ttf", "otf"])
+                    .pick_file(),
+                Message::FontLoad,
+            ),
+            Message::FontLoad(handle) => match handle {
+                Some(file) => Task::perform(App::read_font(file), Message::FontRead),
+                None => Task::none(),
+            },
+            Message::FontRead(result) => {
+                match result {
+                    Ok(font) => {
+                        self.font = Some(font);
+                        self.font_warning = None;
+                    }
+                    Err(warning) => self.font_warning = Some(warning),
+                }
+                Task::none()
+            }
+            Message::ClearFont => {
+                self.font = None;
+                self.font_warning = None;
+                Task::none()
+            }
+            Message::ClearPassphrase => {
+                // Replacing the secrets drops the old values, which zeroizes them
+                self.passphrase = SecretString::default();
+                self.passphrase_confirm = SecretString::default();
+                self.passphrase_score = None;
+                self.passphrase_file = None;
+                Task::none()
+            }
+            Message::ClearSecrets => {
+                // Replacing the secrets drops the old values, which zeroizes them
+                self.passphrase = SecretString::default();
+                self.passphrase_confirm = SecretString::default();
+                self.passphrase_score = None;
+                self.passphrase_file = None;
+                self.secret_content = text_editor::Content::new();
+                self.secret_undo.clear();
+                self.secret_redo.clear();
+                self.secret_chars = 0;
+                self.secret_bytes = 0;
+                self.secret_file_names.clear();
+                self.secret_file_contents.clear();
+                self.deferred.retain(|message| !message.touches_secrets());
+                // The address may hold credentials
+                #[cfg(feature = "url")]
+                {
+                    self.secret_url = String::new();
+                }
+                self.batch_outcomes.clear();
+                self.decrypt_passphrase = SecretString::default();
+                self.decrypt_result = None;
+                self.idle_remaining = None;
+                self.secret_check_due = None;
+                self.key_check_due = None;
+                self.update_secret_size();
+                Task::done(Message::ResetWarning)
+            }
+            Message::NewSecret => {
+                // The sheet of the last secret shouldn't linger next to the
+                // form for the next one
+                self.qr_thumbnail = None;
+                self.last_saved_path = None;
+                Task::done(Message::ClearSecrets).chain(self.focus_first_field())
             }
             Message::GenerateWarning(warning) => {
                 self.generate_warning = Some(warning);
                 Task::none()
             }
+            Message::GenerateErrorDetails(details) => {
+                self.generate_error_details = Some(details);
+                Task::none()
+            }
             Message::ToggleSecretSource(b) => {
                 self.is_file_secret = b;
+                self.update_secret_size();
+                self.save_preferences();
+                self.focus_first_field()
+            }
+            Message::ToggleBatch(b) => {
+                self.batch = b;
+                self.batch_outcomes.clear();
+                self.update_secret_size();
                 Task::none()
             }
-            Message::SecretFileChanged(content) => {
-                self.secret_file_content = Some(content);
+            Message::ToggleSeparateFiles(b) => {
+                self.separate_files = b;
+                self.batch_outcomes.clear();
+                self.update_secret_size();
                 Task::none()
             }
-            Message::SecretFilePick => {
-                if self.secret_file_loading {
-                    Task::none()
+            Message::SecretFileChanged(name, content) => {
+                if let Some(index) = self.secret_file_names.iter().position(|n| *n == name) {
+                    self.secret_file_contents[index] = content;
                 } else {
-                    Task::perform(App::pick_secret(), Message::SecretFileLoad)
+                    self.secret_file_names.push(name);
+                    self.secret_file_contents.push(content);
+                }
+                self.update_secret_size();
+                Task::none()
+            }
+            Message::SecretFileRemove(index) => {
+                if index < self.secret_file_names.len() {
+                    self.secret_file_names.remove(index);
+                    self.secret_file_contents.remove(index);
                 }
+                self.update_secret_size();
+                Task::none()
             }
-            Message::SecretFileLoad(handle) => {
-                if let Some(f) = handle {
-                    self.secret_file_name = Some(f.file_name().into());
+            Message::SecretFilePick => {
+                // Only one dialog at a time, and the shortcut does nothing in text mode
+                if self.secret_file_loading || !self.is_file_secret {
+                    Task::none()
+                } else {
+                    self.secret_file_loading = true;
                     Task::perform(
-                        async move { f.read().await.into() },
-                        Message::SecretFileChanged,
+                        App::pick_secret(self.file_dialog()),
+                        Message::SecretFileLoad,
                     )
-                } else {
+                }
+            }
+            Message::SecretFileLoad(handles) => {
+                self.secret_file_loading = false;
+                Task::batch(handles.into_iter().flatten().map(|f| {
+                    Task::perform(App::read_secret_file(f), |result| match result {
+                        Ok((name, content)) => Message::SecretFileChanged(name, content),
+                        Err(warning) => Message::SecretWarning(warning),
+                    })
+                }))
+            }
+            #[cfg(feature = "url")]
+            Message::SecretUrlChanged(url) => {
+                self.secret_url = url;
+                Task::none()
+            }
+            #[cfg(feature = "url")]
+            Message::SecretUrlLoad => {
+                if self.secret_url_loading || self.secret_url.trim().is_empty() {
+                    return Task::none();
+                }
+                self.secret_url_loading = true;
+                Task::perform(
+                    App::read_secret_url(self.secret_url.trim().to_string()),
+                    Message::SecretUrlLoaded,
+                )
+            }
+            #[cfg(feature = "url")]
+            Message::SecretUrlLoaded(result) => {
+                self.secret_url_loading = false;
+                Task::done(match result {
+                    Ok((name, content)) => Message::SecretFileChanged(name, content),
+                    Err(warning) => Message::SecretWarning(warning),
+                })
+            }
+            Message::FileHovered(hovered) => {
+                self.secret_file_hovered = hovered;
+                Task::none()
+            }
+            Message::FileDropped(path) => {
+                self.secret_file_hovered = false;
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    let load =
+                        Task::done(Message::SecretFileLoad(Some(vec![FileHandle::from(path)])));
+                    if self.is_file_secret {
+                        load
+                    } else {
+                        Task::done(Message::ToggleSecretSource(true)).chain(load)
+                    }
+                }
+                // Browsers give no path to read, so the mode stays as it is
+                #[cfg(target_arch = "wasm32")]
+                {
+                    let _ = path;
                     Task::none()
                 }
             }
             Message::PageSizeChanged(page_size) => {
                 self.page_size = page_size;
+                self.update_secret_size();
+                self.save_preferences();
+                Task::none()
+            }
+            Message::EccLevelChanged(ecc_level) => {
+                self.ecc_level = ecc_level;
+                self.update_secret_size();
+                Task::none()
+            }
+            Message::ToggleGrid(grid) => {
+                self.grid = grid;
+                Task::none()
+            }
+            Message::TogglePrintPlaintext(print_plaintext) => {
+                self.print_plaintext = print_plaintext;
+                Task::none()
+            }
+            Message::ToggleDateStamp(date_stamp) => {
+                self.date_stamp = date_stamp;
+                self.save_preferences();
+                Task::none()
+            }
+            Message::ToggleArmor(armor) => {
+                self.armor = armor;
+                self.update_secret_size();
+                self.save_preferences();
+                Task::none()
+            }
+            Message::ToggleBinaryQr(binary_qr) => {
+                self.binary_qr = binary_qr;
+                self.update_secret_size();
+                self.save_preferences();
+                Task::none()
+            }
+            Message::ToggleInstructions(instructions) => {
+                self.instructions = instructions;
+                self.save_preferences();
+                Task::none()
+            }
+            Message::ToggleVerify(verify) => {
+                self.verify = verify;
+                self.save_preferences();
+                Task::none()
+            }
+            Message::ToggleOpenAfterSave(open_after_save) => {
+                self.open_after_save = open_after_save;
+                self.save_preferences();
+                Task::none()
+            }
+            Message::ToggleClipboardGenerate(clipboard_generate) => {
+                self.clipboard_generate = clipboard_generate;
+                self.save_preferences();
+                Task::none()
+            }
+            #[cfg(feature = "tray")]
+            Message::ToggleTray(tray) => {
+                self.tray = tray;
+                self.save_preferences();
+                Task::none()
+            }
+            Message::ToggleTrimSecret(trim_secret) => {
+                self.trim_secret = trim_secret;
+                self.update_secret_size();
+                self.save_preferences();
+                Task::none()
+            }
+            Message::ToggleNormalizeNewlines(normalize_newlines) => {
+                self.normalize_newlines = normalize_newlines;
+                self.update_secret_size();
+                self.save_preferences();
+                Task::none()
+            }
+            Message::EncodingChanged(encoding) => {
+                self.encoding = encoding;
+                self.update_secret_size();
+                Task::none()
+            }
+            Message::ArchiveFormatChanged(archive_format) => {
+                self.archive_format = archive_format;
+                self.update_secret_size();
+                self.save_preferences();
+                Task::none()
+            }
+            Message::ToggleHeading(show) => {
+                self.heading = show.then(ArcStr::new);
+                self.save_preferences();
+                Task::none()
+            }
+            Message::HeadingChanged(heading) => {
+                self.heading = Some(heading.into());
+                self.save_preferences();
+                Task::none()
+            }
+            Message::ThemeChanged(theme) => {
+                self.theme = theme;
+                self.save_preferences();
+                Task::none()
+            }
+            Message::SystemThemeChanged(mode) => {
+                self.system_theme = mode;
+                Task::none()
+            }
+            Message::TextScaleChanged(scale) => {
+                self.text_scale = scale;
+                self.save_preferences();
+                Task::none()
+            }
+            Message::LanguageChanged(language) => {
+                i18n::set_language(language);
+                self.update_secret_size();
+                self.save_preferences();
+                Task::none()
+            }
+            Message::IdleTimeoutChanged(minutes) => {
+                self.idle_timeout = minutes;
+                self.last_interaction = Instant::now();
+                self.idle_remaining = None;
+                self.save_preferences();
                 Task::none()
             }
+            Message::CiphertextTick => {
+                if self.ciphertext_due.is_none_or(|due| Instant::now() < due) {
+                    return Task::none();
+                }
+                self.ciphertext_due = None;
+                // Every sheet has its own ciphertext, so the estimate has to do
+                if self.splits_sheets() {
+                    self.qr_thumbnail = None;
+                    return Task::none();
+                }
+                let revision = self.qr_revision;
+                Task::perform(
+                    App::encrypt_preview(
+                        self.secret(),
+                        self.key_input(),
+                        self.ecc_level,
+                        self.armor,
+                        self.binary_qr,
+                    ),
+                    move |result| Message::CiphertextReady(revision, result),
+                )
+            }
+            Message::CiphertextReady(revision, result) => {
+                // The inputs changed while encrypting, a newer result is on its way
+                if revision != self.qr_revision {
+                    return Task::none();
+                }
+                let (ciphertext, png) = result.unzip();
+                self.ciphertext = ciphertext;
+                self.qr_thumbnail = png
+                    .flatten()
+                    .map(|png| image::Handle::from_bytes(png.to_vec()));
+                self.update_secret_warning();
+                Task::none()
+            }
+            Message::ValidationTick => {
+                let now = Instant::now();
+                if self.secret_check_due.is_some_and(|due| now >= due) {
+                    self.secret_check_due = None;
+                    self.check_secret();
+                }
+                if self.key_check_due.is_some_and(|due| now >= due) {
+                    self.key_check_due = None;
+                    self.check_key();
+                }
+                Task::none()
+            }
+            Message::IdleTick => {
+                let timeout = Duration::from_secs(self.idle_timeout * 60);
+                let idle = self.last_interaction.elapsed();
+                if idle >= timeout {
+                    self.idle_remaining = None;
+                    self.idle_cleared = true;
+                    Task::done(Message::ClearSecrets)
+                } else {
+                    self.idle_remaining = Some(timeout - idle);
+                    Task::none()
+                }
+            }
+        }
+    }
+
+    fn subscription(&self) -> Subscription<Message> {
+        // Window events such as key presses and dropped files are routed to
+        // the form of their window by `Windows::subscription`
+        let copy_notice = if self.passphrase_copied {
+            iced::time::every(Duration::from_secs(2)).map(|_| Message::ClearCopyNotice)
+        } else {
+            Subscription::none()
+        };
+        let toast = if self.toast.is_some() {
+            iced::time::every(Duration::from_secs(3)).map(|_| Message::ClearToast)
+        } else {
+            Subscription::none()
+        };
+        let spinner = if self.is_generating {
+            iced::time::every(Duration::from_millis(16)).map(|_| Message::Tick)
+        } else {
+            Subscription::none()
+        };
+        let idle = if self.has_secrets() {
+            iced::time::every(Duration::from_secs(1)).map(|_| Message::IdleTick)
+        } else {
+            Subscription::none()
+        };
+        let ciphertext = if self.ciphertext_due.is_some() {
+            iced::time::every(Duration::from_millis(100)).map(|_| Message::CiphertextTick)
+        } else {
+            Subscription::none()
+        };
+        let validation = if self.secret_check_due.is_some() || self.key_check_due.is_some() {
+            iced::time::every(Duration::from_millis(100)).map(|_| Message::ValidationTick)
+        } else {
+            Subscription::none()
+        };
+        let system_theme = if self.theme == AppTheme::System {
+            iced::system::theme_changes().map(Message::SystemThemeChanged)
+        } else {
+            Subscription::none()
+        };
+        Subscription::batch([
+            copy_notice,
+            toast,
+            spinner,
+            idle,
+            ciphertext,
+            validation,
+            system_theme,
+        ])
+    }
+
+    fn shortcut(key: &keyboard::Key, modifiers: keyboard::Modifiers) -> Option<Message> {
+        match key.as_ref() {
+            keyboard::Key::Named(key::Named::Enter) if modifiers.command() => {
+                Some(Message::GeneratePdf)
+            }
+            keyboard::Key::Named(key::Named::Escape) => Some(Message::ResetWarning),
+            keyboard::Key::Named(key::Named::Tab) if !modifiers.command() => {
+                Some(if modifiers.shift() {
+                    Message::FocusPrevious
+                } else {
+                    Message::FocusNext
+                })
+            }
+            keyboard::Key::Character(c) if modifiers.command() && c.eq_ignore_ascii_case("z") => {
+                Some(if modifiers.shift() {
+                    Message::SecretRedo
+                } else {
+                    Message::SecretUndo
+                })
+            }
+            keyboard::Key::Character(c)
+                if modifiers.command() && modifiers.shift() && c.eq_ignore_ascii_case("v") =>
+            {
+                Some(Message::EncryptClipboard)
+            }
+            // Ctrl+N alone opens a window where there can be several
+            keyboard::Key::Character(c)
+                if modifiers.command()
+                    && (modifiers.shift() || cfg!(target_arch = "wasm32"))
+                    && c.eq_ignore_ascii_case("n") =>
+            {
+                Some(Message::NewSecret)
+            }
+            keyboard::Key::Character(c) if modifiers.command() && c.eq_ignore_ascii_case("y") => {
+                Some(Message::SecretRedo)
+            }
+            keyboard::Key::Character(c) if modifiers.command() && c.eq_ignore_ascii_case("o") => {
+                Some(Message::SecretFilePick)
+            }
+            keyboard::Key::Character(c) if modifiers.command() && c.eq_ignore_ascii_case("s") => {
+                Some(Message::GenerateAndSave)
+            }
+            _ => None,
+        }
+    }
+
+    fn title(&self) -> String {
+        if self.title.is_empty() {
+            "PaperAge".to_string()
+        } else {
+            format!("PaperAge — {}", self.title)
+        }
+    }
+
+    fn theme(&self) -> Theme {
+        match &self.theme {
+            AppTheme::Fixed(theme) => theme.clone(),
+            AppTheme::System => match self.system_theme {
+                theme::Mode::Light => Theme::Light,
+                theme::Mode::Dark => Theme::Dark,
+                // The OS did not report an appearance
+                theme::Mode::None => Theme::CatppuccinMocha,
+            },
+        }
+    }
+
+    fn view(&self) -> Element<'_, Message> {
+        let logo = svg(Handle::from_memory(Assets.logo().content()))
+            .height(Length::Fixed(100.0))
+            .style(|theme: &Theme, _| svg::Style {
+                color: Some(theme.palette().text),
+            });
+        let mode_button = |label, mode| {
+            button(text(label))
+                .on_press(Message::SwitchMode(mode))
+                .style(if self.mode == mode {
+                    button::primary
+                } else {
+                    button::secondary
+                })
+        };
+        let body = match self.mode {
+            AppMode::Encrypt => self.encrypt_view(),
+            AppMode::Decrypt => self.decrypt_view().into(),
+        };
+        let toast = self.toast.as_ref().map(|toast| {
+            container(
+                container(
+                    text(toast.as_str())
+                        .size(self.scaled(TEXT_SIZE))
+                        .style(text::success),
+                )
+                .padding([5, 15])
+                .style(container::rounded_box),
+            )
+            .center_x(Fill)
+        });
+        let max_width = if self.mode == AppMode::Encrypt && self.is_wide() {
+            WIDE_LAYOUT_WIDTH
+        } else {
+            400.0
+        };
+        let heading = self.heading.as_ref().map(|heading| {
+            let heading = if heading.is_empty() {
+                tr("app-title")
+            } else {
+                heading.to_string()
+            };
+            container(text(heading).size(self.scaled(35.0))).center_x(Fill)
+        });
+        let content = scrollable(
+            container(
+                container(
+                    column![logo]
+                        .push(heading)
+                        .push(
+                            container(
+                                row![
+                                    mode_button(tr("mode-encrypt"), AppMode::Encrypt),
+                                    mode_button(tr("mode-decrypt"), AppMode::Decrypt),
+                                ]
+                                .spacing(10),
+                            )
+                            .center_x(Fill),
+                        )
+                        .push(body)
+                        .push(toast)
+                        .push(self.about_view())
+                        .spacing(10),
+                )
+                .max_width(max_width),
+            )
+            .padding(if self.window_width < COMPACT_LAYOUT_WIDTH {
+                10
+            } else {
+                30
+            })
+            .center_x(Fill),
+        )
+        .id(CONTENT_ID);
+        let dialog = if self.close_request.is_some() {
+            Some(self.close_dialog())
+        } else if let Some(warning) = &self.generate_warning {
+            Some(self.error_dialog(warning))
+        } else {
+            self.is_generating.then(|| self.busy_dialog())
+        };
+        // Widget state follows the position in the tree, so the content stays
+        // the first layer with or without a dialog to keep its scroll position
+        stack![content].push(dialog.map(App::modal)).into()
+    }
+
+    /// Whether the window is wide enough for the secret and the options side by side
+    fn is_wide(&self) -> bool {
+        self.window_width >= WIDE_LAYOUT_WIDTH
+    }
+
+    /// Which secret Generate PDF encrypts, and a note if the other source holds
+    /// data too. Both are kept when switching, so either could be meant.
+    fn secret_source(&self) -> Column<'_, Message> {
+        let other_has_data = if self.is_file_secret {
+            !self.editor_text().trim().is_empty()
+        } else {
+            !self.secret_file_names.is_empty()
+        };
+        column![
+            text(tr(if self.is_file_secret {
+                "secret-source-files"
+            } else {
+                "secret-source-text"
+            }))
+            .size(self.scaled(10.0))
+        ]
+        .push(other_has_data.then(|| {
+            text(tr(if self.is_file_secret {
+                "secret-source-text-unused"
+            } else {
+                "secret-source-files-unused"
+            }))
+            .size(self.scaled(10.0))
+            .style(text::warning)
+        }))
+    }
+
+    /// A warning with an icon in front, so it doesn't rely on color alone. The
+    /// message stays plain text, iced has no accessibility tree to announce it
+    /// through yet.
+    fn warning<'a>(&self, warning: Option<&'a str>, size: f32) -> Element<'a, Message> {
+        let Some(warning) = warning else {
+            return row![].into();
+        };
+        row![
+            svg(Handle::from_memory(
+                Assets.icons().error_warning_line().content()
+            ))
+            .width(Length::Fixed(size))
+            .height(Length::Fixed(size))
+            .style(|theme: &Theme, _| svg::Style {
+                color: Some(theme.palette().danger),
+            }),
+            text(warning).size(size).style(text::danger),
+        ]
+        .spacing(5)
+        .align_y(iced::alignment::Vertical::Center)
+        .into()
+    }
+
+    /// Address field and button to load a secret file from a URL
+    #[cfg(feature = "url")]
+    fn secret_url_input(&self) -> Element<'_, Message> {
+        let can_load = !self.secret_url_loading && !self.secret_url.trim().is_empty();
+        row![
+            text_input(&tr("secret-url-placeholder"), &self.secret_url)
+                .on_input(Message::SecretUrlChanged)
+                .on_submit(Message::SecretUrlLoad)
+                .size(self.scaled(TEXT_SIZE)),
+            button(text(tr("secret-url-load")))
+                .on_press_maybe(can_load.then_some(Message::SecretUrlLoad))
+                .style(if self.secret_url_loading {
+                    button::secondary
+                } else {
+                    button::primary
+                }),
+        ]
+        .spacing(5)
+        .padding(iced::Padding::ZERO.bottom(10))
+        .align_y(iced::alignment::Vertical::Center)
+        .into()
+    }
+
+    #[cfg(not(feature = "url"))]
+    fn secret_url_input(&self) -> Element<'_, Message> {
+        row![].into()
+    }
+
+    #[cfg(feature = "tray")]
+    fn tray_toggle(&self) -> Option<Element<'_, Message>> {
+        Some(
+            toggler(self.tray)
+                .label(tr("tray-toggle"))
+                .on_toggle(Message::ToggleTray)
+                .into(),
+        )
+    }
+
+    #[cfg(not(feature = "tray"))]
+    fn tray_toggle(&self) -> Option<Element<'_, Message>> {
+        None
+    }
+
+    /// Asterisk after the label of a field that must be filled in to generate
+    fn required_marker(&self) -> Text<'_> {
+        text("*").size(self.scaled(TEXT_SIZE)).style(text::danger)
+    }
+
+    /// Small button next to a field that empties just that field
+    fn clear_button(&self, message: Message) -> Element<'_, Message> {
+        button(text("×").size(self.scaled(TEXT_SIZE)))
+            .on_press(message)
+            .style(button::secondary)
+            .into()
+    }
+
+    /// Dropdown of earlier values for a field, hidden while there are none
+    fn history_list(
+        &self,
+        history: &[String],
+        on_select: fn(String) -> Message,
+    ) -> Option<Element<'_, Message>> {
+        (!history.is_empty()).then(|| {
+            pick_list(history.to_vec(), None::<String>, on_select)
+                .placeholder(tr("history-placeholder"))
+                .text_size(self.scaled(12.0))
+                .width(Length::Shrink)
+                .into()
+        })
+    }
+
+    /// Button with an arrow that shows or hides a section below it
+    fn spoiler_button<'a>(label: String, open: bool, message: Message) -> Element<'a, Message> {
+        let arrow_icon = if open {
+            svg(Handle::from_memory(
+                Assets.icons().arrow_drop_down_line().content(),
+            ))
+        } else {
+            svg(Handle::from_memory(
+                Assets.icons().arrow_drop_right_line().content(),
+            ))
+        }
+        .height(Length::Fixed(24.0))
+        .width(Length::Fixed(12.0))
+        .style(|theme: &Theme, _| svg::Style {
+            color: Some(theme.palette().background),
+        })
+        .content_fit(iced::ContentFit::ScaleDown);
+        button(row![arrow_icon, text(label)].align_y(iced::alignment::Vertical::Center))
+            .on_press(message)
+            .into()
+    }
+
+    /// What the fields mean and how a sheet is used, as plain text
+    fn help_view(&self) -> Column<'_, Message> {
+        [
+            ("help-fields-title", "help-fields"),
+            ("help-file-title", "help-file"),
+            ("help-recovery-title", "help-recovery"),
+            ("help-passphrase-title", "help-passphrase"),
+        ]
+        .into_iter()
+        .fold(Column::new(), |help, (title, body)| {
+            help.push(text(tr(title)).size(self.scaled(TEXT_SIZE)))
+                .push(text(tr(body)).size(self.scaled(12.0)))
+        })
+        .spacing(5)
+    }
+
+    fn about_view(&self) -> Column<'_, Message> {
+        let toggle = container(
+            button(text(tr("about")).size(self.scaled(10.0)))
+                .on_press(Message::ToggleAbout)
+                .style(button::text),
+        )
+        .center_x(Fill);
+        if !self.show_about {
+            return column![toggle];
+        }
+        let version = |label: &str, version: &'static str| {
+            text(format!("{label} {version}")).size(self.scaled(10.0))
+        };
+        column![
+            toggle,
+            container(
+                column![
+                    version("PaperAge GUI", env!("CARGO_PKG_VERSION")),
+                    version("paper-age", env!("PAPER_AGE_VERSION")),
+                    version("age", env!("AGE_VERSION")),
+                    button(text(env!("CARGO_PKG_REPOSITORY")).size(self.scaled(10.0)))
+                        .on_press(Message::OpenRepository)
+                        .style(button::text)
+                        .padding(0),
+                ]
+                .spacing(2),
+            )
+            .padding(10)
+            .width(Fill)
+            .style(container::rounded_box),
+        ]
+        .spacing(5)
+    }
+
+    fn error_dialog<'a>(&'a self, warning: &'a str) -> Element<'a, Message> {
+        let details = self
+            .generate_error_details
+            .as_ref()
+            .map(|details| text(details.as_str()).size(self.scaled(10.0)));
+        container(
+            column![self.warning(Some(warning), self.scaled(TEXT_SIZE))]
+                .push(details)
+                .push(
+                    container(
+                        button(text(tr("dismiss")).size(self.scaled(TEXT_SIZE)))
+                            .on_press(Message::ResetWarning),
+                    )
+                    .align_right(Fill),
+                )
+                .spacing(10),
+        )
+        .width(360)
+        .padding(20)
+        .style(container::rounded_box)
+        .into()
+    }
+
+    fn close_dialog(&self) -> Element<'_, Message> {
+        container(
+            column![
+                text(tr("close-unsaved")).size(self.scaled(TEXT_SIZE)),
+                container(
+                    row![
+                        button(text(tr("cancel")).size(self.scaled(TEXT_SIZE)))
+                            .style(button::secondary)
+                            .on_press(Message::ResetWarning),
+                        button(text(tr("close-discard")).size(self.scaled(TEXT_SIZE)))
+                            .style(button::danger)
+                            .on_press(Message::ConfirmClose),
+                    ]
+                    .spacing(10),
+                )
+                .align_right(Fill),
+            ]
+            .spacing(10),
+        )
+        .width(360)
+        .padding(20)
+        .style(container::rounded_box)
+        .into()
+    }
+
+    /// Progress of the running generation with the only way out of it, shown
+    /// over the form so its inputs can't change underneath the task
+    fn busy_dialog(&self) -> Element<'_, Message> {
+        let progress: Element<'_, Message> = if let Some(progress) = self.generate_progress {
+            progress_bar(0.0..=1.0, progress)
+                .length(Length::Fixed(160.0))
+                .girth(8)
+                .into()
+        } else {
+            svg(Handle::from_memory(Assets.icons().loader_line().content()))
+                .height(Length::Fixed(20.0))
+                .width(Length::Fixed(20.0))
+                .rotation(iced::Radians(self.spinner_rotation))
+                .style(|theme: &Theme, _| svg::Style {
+                    color: Some(theme.palette().primary),
+                })
+                .into()
+        };
+        container(
+            column![
+                row![
+                    progress,
+                    text(tr("generating")).size(self.scaled(TEXT_SIZE)),
+                ]
+                .spacing(10)
+                .align_y(iced::alignment::Vertical::Center),
+                container(
+                    button(text(tr("cancel")).size(self.scaled(TEXT_SIZE)))
+                        .style(button::secondary)
+                        .on_press(Message::CancelGenerate),
+                )
+                .align_right(Fill),
+            ]
+            .spacing(10),
+        )
+        .width(360)
+        .padding(20)
+        .style(container::rounded_box)
+        .into()
+    }
+
+    /// Layer showing `dialog` above the content, dimming and blocking it until dismissed
+    fn modal<'a>(dialog: impl Into<Element<'a, Message>>) -> Element<'a, Message> {
+        opaque(
+            mouse_area(center(opaque(dialog)).style(|_theme| {
+                container::Style {
+                    background: Some(
+                        Color {
+                            a: 0.8,
+                            ..Color::BLACK
+                        }
+                        .into(),
+                    ),
+                    ..container::Style::default()
+                }
+            }))
+            .on_press(Message::ResetWarning),
+        )
+    }
+
+    fn decrypt_view(&self) -> Column<'_, Message> {
+        let result = match &self.decrypt_result {
+            Some(plaintext) => match std::str::from_utf8(plaintext) {
+                Ok(plaintext) => column![
+                    text(tr("decrypted-secret")).size(self.scaled(TEXT_SIZE)),
+                    container(
+                        text(plaintext)
+                            .size(self.scaled(TEXT_SIZE))
+                            .font(iced::Font::MONOSPACE)
+                    )
+                    .padding(10)
+                    .width(Fill)
+                    .style(container::bordered_box),
+                ],
+                Err(_) => match ArchiveFormat::detect(plaintext)
+                    .and_then(|format| Some((format, format.list(plaintext)?)))
+                {
+                    Some((format, files)) => column![text(tr_args(
+                        "decrypted-archive",
+                        &fluent_args!["format" => format.to_string(), "count" => files.len()]
+                    ))]
+                    .extend(files.into_iter().map(|(name, size)| {
+                        text(tr_args(
+                            "decrypted-archive-file",
+                            &fluent_args!["name" => name, "bytes" => size],
+                        ))
+                        .size(self.scaled(TEXT_SIZE))
+                        .font(iced::Font::MONOSPACE)
+                        .into()
+                    })),
+                    None => column![text(tr_args(
+                        "decrypted-binary",
+                        &fluent_args!["bytes" => plaintext.len()]
+                    ))],
+                },
+            }
+            .spacing(5),
+            None => column![],
+        };
+        column![
+            text(tr("decrypt-pdf-label")).size(self.scaled(TEXT_SIZE)),
+            row![
+                button(text(tr("decrypt-open"))).on_press(Message::DecryptFilePick),
+                container(
+                    text(
+                        self.decrypt_pdfs
+                            .iter()
+                            .map(|(name, _)| name.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                    .width(Length::Fill)
+                )
+                .padding(15),
+            ]
+            .align_y(iced::alignment::Vertical::Center),
+            text(tr("decrypt-parts-hint")).size(self.scaled(10.0)),
+            text(tr("passphrase-label")).size(self.scaled(TEXT_SIZE)),
+            text_input(
+                &tr("passphrase-placeholder"),
+                self.decrypt_passphrase.expose_secret()
+            )
+            .on_input(Message::DecryptPassphraseChanged)
+            .size(self.scaled(TEXT_SIZE))
+            .on_submit(Message::Decrypt)
+            .secure(true),
+            container(
+                column![
+                    button(text(tr("decrypt-button")))
+                        .on_press(Message::Decrypt)
+                        .style(if self.is_decrypting {
+                            button::secondary
+                        } else {
+                            button::primary
+                        }),
+                    text(
+                        self.decrypt_warning
+                            .as_ref()
+                            .map(ArcStr::as_str)
+                            .unwrap_or_default()
+                    )
+                    .size(self.scaled(10.0))
+                    .style(text::danger),
+                ]
+                .align_x(iced::alignment::Horizontal::Center)
+            )
+            .center_x(Fill),
+            result,
+        ]
+        .spacing(10)
+    }
+
+    fn encrypt_view(&self) -> Element<'_, Message> {
+        let extra_button =
+            App::spoiler_button(tr("extra"), self.show_extra, Message::ToggleExtraSpoiler);
+        // The caption and the date share the line of the notes label on the page
+        let notes_line = pdf::notes_line(&self.notes_label, &self.qr_caption, self.date_stamp);
+        let notes_line_len = notes_line.chars().count();
+        let font_warning = self
+            .font_warning
+            .as_ref()
+            .map(ArcStr::to_string)
+            .or_else(|| {
+                let (_, font) = self.font.as_ref()?;
+                let c = font
+                    .missing_glyph(&self.title)
+                    .or_else(|| font.missing_glyph(&notes_line))?;
+                Some(tr_args(
+                    "warning-font-missing-glyph",
+                    &fluent_args!["char" => c.to_string()],
+                ))
+            });
+        let extra_config = if self.show_extra {
+            column![
+                extra_button,
+                text(tr("profile-label")).size(self.scaled(TEXT_SIZE)),
+                row![
+                    pick_list(
+                        self.profiles
+                            .iter()
+                            .map(|profile| ArcStr::from(profile.name.as_str()))
+                            .collect::<Vec<_>>(),
+                        self.profile.clone(),
+                        Message::ProfileSelected,
+                    )
+                    .placeholder(tr("profile-placeholder"))
+                    .width(Fill),
+                    button(text(tr("profile-delete")))
+                        .on_press_maybe(self.profile.is_some().then_some(Message::DeleteProfile))
+                        .style(button::danger),
+                ]
+                .spacing(5)
+                .align_y(iced::alignment::Vertical::Center),
+                row![
+                    text_input(&tr("profile-name-placeholder"), &self.profile_name)
+                        .on_input(Message::ProfileNameChanged)
+                        .on_submit(Message::SaveProfile)
+                        .size(self.scaled(TEXT_SIZE)),
+                    button(text(tr("profile-save")))
+                        .on_press_maybe(
+                            (!self.profile_name.trim().is_empty()).then_some(Message::SaveProfile)
+                        )
+                        .style(button::secondary),
+                ]
+                .spacing(5)
+                .align_y(iced::alignment::Vertical::Center),
+                text(tr("title-label")).size(self.scaled(TEXT_SIZE)),
+                row![
+                    text_input("PaperAge", &self.title)
+                        .on_input(Message::TitleChanged)
+                        .size(self.scaled(TEXT_SIZE)),
+                ]
+                .push(self.history_list(&self.title_history, Message::TitleChanged))
+                .push(self.clear_button(Message::ClearTitle))
+                .spacing(5)
+                .align_y(iced::alignment::Vertical::Center),
+                row![
+                    text(tr("notes-label")).size(self.scaled(TEXT_SIZE)),
+                    horizontal_space(),
+                    text(tr_args(
+                        "notes-remaining",
+                        &fluent_args![
+                            "count" => NOTES_LABEL_MAX_LEN - self.notes_label.chars().count()
+                        ]
+                    ))
+                    .size(self.scaled(10.0)),
+                ]
+                .align_y(iced::alignment::Vertical::Center),
+                row![
+                    text_input(&tr("notes-placeholder"), &self.notes_label)
+                        .on_input(Message::NotesLabelChanged)
+                        .size(self.scaled(TEXT_SIZE)),
+                ]
+                .push(self.history_list(&self.notes_history, Message::NotesLabelChanged))
+                .push(self.clear_button(Message::ClearNotesLabel))
+                .spacing(5)
+                .align_y(iced::alignment::Vertical::Center),
+                text(
+                    self.notes_warning
+                        .as_ref()
+                        .map(ArcStr::as_str)
+                        .unwrap_or_default()
+                )
+                .size(self.scaled(10.0))
+                .style(text::danger),
+                text(tr("qr-caption-label")).size(self.scaled(TEXT_SIZE)),
+                row![
+                    text_input(&tr("qr-caption-placeholder"), &self.qr_caption)
+                        .on_input(Message::QrCaptionChanged)
+                        .size(self.scaled(TEXT_SIZE)),
+                    self.clear_button(Message::ClearQrCaption),
+                ]
+                .spacing(5)
+                .align_y(iced::alignment::Vertical::Center),
+                text(
+                    self.qr_caption_warning
+                        .as_ref()
+                        .map(ArcStr::as_str)
+                        .unwrap_or_default()
+                )
+                .size(self.scaled(10.0))
+                .style(text::danger),
+                text(if notes_line_len > NOTES_LABEL_MAX_LEN {
+                    tr_args(
+                        "warning-notes-line-too-long",
+                        &fluent_args!["max" => NOTES_LABEL_MAX_LEN],
+                    )
+                } else {
+                    String::new()
+                })
+                .size(self.scaled(10.0))
+                .style(text::warning),
+                text(tr("author-label")).size(self.scaled(TEXT_SIZE)),
+                text_input("", &self.author)
+                    .on_input(Message::AuthorChanged)
+                    .size(self.scaled(TEXT_SIZE)),
+                text(tr("subject-label")).size(self.scaled(TEXT_SIZE)),
+                text_input("", &self.subject)
+                    .on_input(Message::SubjectChanged)
+                    .size(self.scaled(TEXT_SIZE)),
+                text(tr("metadata-hint")).size(self.scaled(10.0)),
+                text(tr("font-label")).size(self.scaled(TEXT_SIZE)),
+                row![
+                    button(
+                        text(match &self.font {
+                            Some((name, _)) => name.to_string(),
+                            None => tr("font-default"),
+                        })
+                        .size(self.scaled(TEXT_SIZE))
+                    )
+                    .on_press(Message::FontPick)
+                    .style(button::secondary),
+                    self.clear_button(Message::ClearFont),
+                ]
+                .spacing(5)
+                .align_y(iced::alignment::Vertical::Center),
+                text(font_warning.unwrap_or_default())
+                    .size(self.scaled(10.0))
+                    .style(text::warning),
+                button(text(tr("clear-history")).size(self.scaled(10.0)))
+                    .on_press_maybe(
+                        (!self.title_history.is_empty() || !self.notes_history.is_empty())
+                            .then_some(Message::ClearInputHistory)
+                    )
+                    .style(button::secondary),
+                text(tr("output-name-label")).size(self.scaled(TEXT_SIZE)),
+                text_input(
+                    &self.default_file_name(),
+                    self.output_name
+                        .as_ref()
+                        .map(ArcStr::as_str)
+                        .unwrap_or_default()
+                )
+                .on_input(Message::OutputNameChanged)
+                .size(self.scaled(TEXT_SIZE)),
+                text(tr("page-size-label")).size(self.scaled(TEXT_SIZE)),
+                pick_list(
+                    PAGE_SIZES.map(PageSizeOption),
+                    Some(PageSizeOption(self.page_size.clone())),
+                    |option| Message::PageSizeChanged(option.0),
+                ),
+                text(tr("ecc-level-label")).size(self.scaled(TEXT_SIZE)),
+                pick_list(
+                    EccLevel::ALL,
+                    Some(self.ecc_level),
+                    Message::EccLevelChanged,
+                ),
+                toggler(self.grid)
+                    .label(tr("grid-toggle"))
+                    .on_toggle(Message::ToggleGrid),
+                toggler(self.print_plaintext)
+                    .label(tr("print-plaintext-toggle"))
+                    .on_toggle(Message::TogglePrintPlaintext),
+                toggler(self.date_stamp)
+                    .label(tr("date-stamp-toggle"))
+                    .on_toggle(Message::ToggleDateStamp),
+                // The raw ciphertext has no armor to keep
+                toggler(self.armor && !self.binary_qr)
+                    .label(tr("armor-toggle"))
+                    .on_toggle_maybe((!self.binary_qr).then_some(Message::ToggleArmor)),
+                toggler(self.binary_qr)
+                    .label(tr("binary-qr-toggle"))
+                    .on_toggle(Message::ToggleBinaryQr),
+                text(if self.binary_qr {
+                    tr("binary-qr-warning")
+                } else {
+                    String::new()
+                })
+                .size(self.scaled(10.0))
+                .style(text::warning),
+                toggler(self.instructions)
+                    .label(tr("instructions-toggle"))
+                    .on_toggle(Message::ToggleInstructions),
+                toggler(self.verify)
+                    .label(tr("verify-toggle"))
+                    .on_toggle(Message::ToggleVerify),
+                toggler(self.open_after_save)
+                    .label(tr("open-after-save-toggle"))
+                    .on_toggle(Message::ToggleOpenAfterSave),
+                toggler(self.clipboard_generate)
+                    .label(tr("clipboard-generate-toggle"))
+                    .on_toggle(Message::ToggleClipboardGenerate),
+                toggler(self.reuse_payload)
+                    .label(tr("reuse-payload-toggle"))
+                    .on_toggle(Message::ToggleReusePayload),
+                text(if self.reuse_payload {
+                    tr("reuse-payload-warning")
+                } else {
+                    String::new()
+                })
+                .size(self.scaled(10.0))
+                .style(text::warning),
+                toggler(self.trim_secret)
+                    .label(tr("trim-secret-toggle"))
+                    .on_toggle(Message::ToggleTrimSecret),
+                toggler(self.normalize_newlines)
+                    .label(tr("normalize-newlines-toggle"))
+                    .on_toggle(Message::ToggleNormalizeNewlines),
+                text(tr("encoding-label")).size(self.scaled(TEXT_SIZE)),
+                pick_list(
+                    TextEncoding::ALL,
+                    Some(self.encoding),
+                    Message::EncodingChanged,
+                ),
+                text(tr("archive-format-label")).size(self.scaled(TEXT_SIZE)),
+                pick_list(
+                    ArchiveFormat::ALL,
+                    Some(self.archive_format),
+                    Message::ArchiveFormatChanged,
+                ),
+                text(tr("passphrase-words-label")).size(self.scaled(TEXT_SIZE)),
+                pick_list(
+                    [4, 5, 6, 7, 8, 9, 10],
+                    Some(self.passphrase_words),
+                    Message::PassphraseWordsChanged,
+                ),
+                toggler(self.heading.is_some())
+                    .label(tr("heading-toggle"))
+                    .on_toggle(Message::ToggleHeading),
+                text_input(
+                    &tr("app-title"),
+                    self.heading
+                        .as_ref()
+                        .map(ArcStr::as_str)
+                        .unwrap_or_default()
+                )
+                .on_input_maybe(self.heading.is_some().then_some(Message::HeadingChanged))
+                .size(self.scaled(TEXT_SIZE)),
+                text(tr("theme-label")).size(self.scaled(TEXT_SIZE)),
+                pick_list(AppTheme::all(), Some(&self.theme), Message::ThemeChanged),
+                text(tr_args(
+                    "text-size-label",
+                    &fluent_args!["percent" => (self.text_scale * 100.0).round()]
+                ))
+                .size(self.scaled(TEXT_SIZE)),
+                slider(TEXT_SCALE_RANGE, self.text_scale, Message::TextScaleChanged).step(0.1_f32),
+                text(tr("language-label")).size(self.scaled(TEXT_SIZE)),
+                pick_list(
+                    Language::ALL,
+                    Some(i18n::language()),
+                    Message::LanguageChanged
+                ),
+                text(tr("idle-timeout-label")).size(self.scaled(TEXT_SIZE)),
+                pick_list(
+                    [1, 2, 5, 10, 15, 30],
+                    Some(self.idle_timeout),
+                    Message::IdleTimeoutChanged,
+                ),
+            ]
+            .push(self.tray_toggle())
+        } else {
+            column![extra_button,]
+        };
+        let secret_input = if self.is_file_secret {
+            column![
+                row![
+                    button(text(tr("secret-open")))
+                        .on_press(Message::SecretFilePick)
+                        .style(if self.secret_file_loading {
+                            button::secondary
+                        } else {
+                            button::primary
+                        }),
+                    container(
+                        text(match self.secret_file_names.len() {
+                            0 => tr("secret-no-files"),
+                            n => tr_args(
+                                "secret-files",
+                                &fluent_args![
+                                    "count" => n,
+                                    "format" => self.archive_format.to_string()
+                                ]
+                            ),
+                        })
+                        .width(Length::Fill)
+                    )
+                    .padding(15),
+                ]
+                .align_y(iced::alignment::Vertical::Center),
+                self.secret_url_input(),
+                container(text(tr("secret-drop")).size(self.scaled(12.0)))
+                    .center_x(Fill)
+                    .padding(15)
+                    .style(if self.secret_file_hovered {
+                        |theme: &Theme| container::Style {
+                            border: iced::Border {
+                                color: theme.palette().primary,
+                                width: 2.0,
+                                radius: 5.0.into(),
+                            },
+                            ..container::bordered_box(theme)
+                        }
+                    } else {
+                        |theme: &Theme| container::Style {
+                            border: iced::Border {
+                                color: theme.extended_palette().background.strong.color,
+                                width: 1.0,
+                                radius: 5.0.into(),
+                            },
+                            ..container::transparent(theme)
+                        }
+                    }),
+                column(
+                    self.secret_file_names
+                        .iter()
+                        .enumerate()
+                        .map(|(index, name)| {
+                            row![
+                                text(name.as_str()).width(Length::Fill),
+                                button(text("x").size(self.scaled(10.0)))
+                                    .on_press(Message::SecretFileRemove(index))
+                                    .style(button::danger),
+                            ]
+                            .align_y(iced::alignment::Vertical::Center)
+                            .into()
+                        })
+                )
+                .spacing(5),
+                self.warning(self.secret_warning.as_deref(), self.scaled(12.0)),
+            ]
+        } else {
+            column![
+                text_editor(&self.secret_content)
+                    .on_action(Message::SecretContentChanged)
+                    .size(self.scaled(TEXT_SIZE))
+                    .id(SECRET_EDITOR_ID)
+                    .key_binding(|key_press| {
+                        // Leave command shortcuts and Tab to the subscription instead of
+                        // inserting text
+                        if (key_press.modifiers.command()
+                            || key_press.key == keyboard::Key::Named(key::Named::Tab))
+                            && App::shortcut(&key_press.key, key_press.modifiers).is_some()
+                        {
+                            None
+                        } else {
+                            text_editor::Binding::from_key_press(key_press)
+                        }
+                    }),
+                row![
+                    button(text(tr("secret-paste")).size(self.scaled(12.0)))
+                        .on_press(Message::SecretPaste),
+                    button(text(tr("secret-undo")).size(self.scaled(12.0))).on_press_maybe(
+                        (!self.secret_undo.is_empty()).then_some(Message::SecretUndo)
+                    ),
+                    button(text(tr("secret-redo")).size(self.scaled(12.0))).on_press_maybe(
+                        (!self.secret_redo.is_empty()).then_some(Message::SecretRedo)
+                    ),
+                    horizontal_space(),
+                    text(tr_args(
+                        "secret-counter",
+                        &fluent_args!["chars" => self.secret_chars, "bytes" => self.secret_bytes]
+                    ))
+                    .size(self.scaled(10.0)),
+                ]
+                .align_y(iced::alignment::Vertical::Center),
+                self.warning(self.secret_warning.as_deref(), self.scaled(12.0)),
+            ]
+        };
+        let preview = if let Some(pdf) = &self.last_pdf {
+            column![
+                container(
+                    self.preview_image
+                        .clone()
+                        .map(|handle| Element::from(image(handle).width(Fill)))
+                        .unwrap_or_else(|| text(tr("preview-rendering")).into())
+                )
+                .padding(5)
+                .style(container::bordered_box)
+                .center_x(Fill),
+                container(
+                    row![
+                        button(text(tr("preview-save")))
+                            .on_press(Message::SaveSecretPdf(pdf.clone())),
+                        button(text(tr("preview-print")))
+                            .on_press(Message::PrintPdf(pdf.clone()))
+                            .style(button::secondary),
+                        button(text(tr("preview-regenerate")))
+                            .on_press_maybe(self.can_generate().then_some(Message::GeneratePdf))
+                            .style(if self.is_generating {
+                                button::secondary
+                            } else {
+                                button::primary
+                            }),
+                    ]
+                    .spacing(10)
+                )
+                .center_x(Fill),
+            ]
+            .spacing(10)
+        } else {
+            column![]
+        };
+        let passphrase_visibility_icon = if self.show_passphrase {
+            svg(Handle::from_memory(Assets.icons().eye_off_line().content()))
+        } else {
+            svg(Handle::from_memory(Assets.icons().eye_line().content()))
+        }
+        .height(Length::Fixed(16.0))
+        .width(Length::Fixed(16.0))
+        .style(|theme: &Theme, _| svg::Style {
+            color: Some(theme.palette().background),
+        });
+        let saved = if let Some(path) = &self.last_saved_path {
+            let saved_text = text(tr_args("saved-to", &fluent_args!["path" => path.as_str()]))
+                .size(self.scaled(10.0))
+                .style(text::success)
+                .width(Fill);
+            #[cfg(not(target_arch = "wasm32"))]
+            let saved_row = row![
+                saved_text,
+                button(text(tr("open-folder")).size(self.scaled(10.0)))
+                    .on_press(Message::OpenSaveFolder),
+            ];
+            #[cfg(target_arch = "wasm32")]
+            let saved_row = row![saved_text];
+            let saved_row = saved_row
+                .push(self.last_pdf.clone().map(|pdf| {
+                    button(text(tr("another-copy")).size(self.scaled(10.0)))
+                        .on_press(Message::SaveSecretPdf(pdf))
+                }))
+                .spacing(5)
+                .align_y(iced::alignment::Vertical::Center);
+            column![saved_row]
+                .push(self.last_pdf.is_some().then(|| {
+                    text(tr("another-copy-hint"))
+                        .size(self.scaled(10.0))
+                        .style(text::secondary)
+                }))
+                .spacing(5)
+        } else {
+            column![]
+        };
+        let fingerprint = text(
+            self.fingerprint
+                .as_ref()
+                .map(|fingerprint| {
+                    tr_args(
+                        "fingerprint",
+                        &fluent_args!["fingerprint" => fingerprint.as_str()],
+                    )
+                })
+                .unwrap_or_default(),
+        )
+        .size(self.scaled(10.0))
+        .font(iced::Font::MONOSPACE);
+        let batch_summary = if self.batch_outcomes.is_empty() {
+            column![]
+        } else {
+            let saved = self
+                .batch_outcomes
+                .iter()
+                .filter(|outcome| outcome.saved_to.is_ok())
+                .count();
+            column![
+                text(tr_args(
+                    "batch-summary",
+                    &fluent_args!["saved" => saved, "total" => self.batch_outcomes.len()]
+                ))
+                .size(self.scaled(10.0))
+            ]
+            .extend(self.batch_outcomes.iter().map(|outcome| {
+                match &outcome.saved_to {
+                    Ok(path) => text(tr_args(
+                        "batch-saved",
+                        &fluent_args![
+                            "label" => outcome.label.as_str(),
+                            "path" => path.as_str(),
+                        ],
+                    ))
+                    .style(text::success),
+                    Err(error) => text(tr_args(
+                        "batch-failed",
+                        &fluent_args![
+                            "label" => outcome.label.as_str(),
+                            "error" => error.as_str(),
+                        ],
+                    ))
+                    .style(text::danger),
+                }
+                .size(self.scaled(10.0))
+                .into()
+            }))
+            .spacing(2)
+        };
+        let qr_thumbnail = column![].push(
+            self.qr_thumbnail
+                .clone()
+                .map(|handle| image(handle).width(Length::Fixed(120.0))),
+        );
+        let idle_notice = if self.idle_cleared {
+            text(tr("idle-cleared"))
+                .size(self.scaled(10.0))
+                .style(text::warning)
+        } else if let Some(remaining) = self.idle_remaining.filter(|r| r.as_secs() < 60) {
+            text(tr_args(
+                "idle-countdown",
+                &fluent_args!["seconds" => remaining.as_secs() + 1],
+            ))
+            .size(self.scaled(10.0))
+            .style(text::warning)
+        } else {
+            text("")
+        };
+        let passphrase_confirm = if self.show_passphrase {
+            column![]
+        } else {
+            column![
+                text_input(
+                    &tr("passphrase-confirm-placeholder"),
+                    self.passphrase_confirm.expose_secret()
+                )
+                .on_input(Message::PassphraseConfirmChanged)
+                .on_submit(Message::GeneratePdf)
+                .size(self.scaled(TEXT_SIZE))
+                .secure(true),
+            ]
+        };
+        let usage = self.usage(&self.page_size);
+        let capacity_gauge = column![
+            progress_bar(0.0..=1.0, usage)
+                .girth(6)
+                .style(if usage > 1.0 {
+                    progress_bar::danger
+                } else if usage > capacity::GAUGE_THRESHOLD {
+                    progress_bar::warning
+                } else {
+                    progress_bar::success
+                }),
+            text(tr_args(
+                "secret-size",
+                &fluent_args![
+                    "percent" => (usage * 100.0).round(),
+                    "page" => PageSizeOption(self.page_size.clone()).name(),
+                    "ecc" => self.ecc_level.to_string(),
+                    "size" => self.secret_size,
+                    "encrypted" => self.payload_len(),
+                    "capacity" => capacity::qr_capacity(&self.page_size, self.ecc_level),
+                ]
+            ))
+            .size(self.scaled(10.0)),
+        ]
+        .push(
+            (usage > 1.0 && !self.splits_sheets())
+                .then(|| capacity::sheets_needed(self.secret_size, &self.page_size, self.ecc_level))
+                .filter(|&count| count <= split::MAX_PARTS)
+                .map(|count| {
+                    button(
+                        text(tr_args("split-generate", &fluent_args!["count" => count]))
+                            .size(self.scaled(12.0)),
+                    )
+                    .on_press(Message::GenerateSplit)
+                    .style(button::secondary)
+                }),
+        )
+        .spacing(3);
+        let passphrase_strength = if let Some(score) = self.passphrase_score {
+            let label = tr(match score {
+                0 => "strength-very-weak",
+                1 => "strength-weak",
+                2 => "strength-fair",
+                3 => "strength-strong",
+                _ => "strength-very-strong",
+            });
+            column![
+                progress_bar(0.0..=4.0, f32::from(score) + 0.5)
+                    .girth(4)
+                    .style(match score {
+                        0 | 1 => progress_bar::danger,
+                        2 => progress_bar::warning,
+                        _ => progress_bar::success,
+                    }),
+                text(if score <= 1 {
+                    tr_args("strength-hint", &fluent_args!["label" => label])
+                } else {
+                    label
+                })
+                .size(self.scaled(10.0))
+                .style(if score <= 1 {
+                    text::warning
+                } else {
+                    text::default
+                }),
+            ]
+            .spacing(2)
+        } else {
+            column![]
+        };
+        let key_input = if self.use_recipients {
+            column![
+                text_input("age1...", &self.recipients)
+                    .id(RECIPIENTS_ID)
+                    .on_input(Message::RecipientsChanged)
+                    .on_submit(Message::GeneratePdf)
+                    .size(self.scaled(TEXT_SIZE)),
+            ]
+        } else {
+            column![
+                row![
+                    text_input(
+                        &tr("passphrase-placeholder"),
+                        self.passphrase.expose_secret()
+                    )
+                    .id(PASSPHRASE_ID)
+                    .on_input(Message::PassphraseChanged)
+                    // Move on to the confirmation while it is shown
+                    .on_submit(if self.show_passphrase {
+                        Message::GeneratePdf
+                    } else {
+                        Message::FocusNext
+                    })
+                    .size(self.scaled(TEXT_SIZE))
+                    .secure(!self.show_passphrase),
+                    button(passphrase_visibility_icon)
+                        .on_press(Message::TogglePassphraseVisibility(!self.show_passphrase)),
+                    button(text(tr("passphrase-generate"))).on_press(Message::GeneratePassphrase),
+                    button(text(tr("passphrase-copy"))).on_press(Message::CopyPassphrase),
+                    self.clear_button(Message::ClearPassphrase),
+                ]
+                .spacing(5)
+                .align_y(iced::alignment::Vertical::Center),
+                row![
+                    button(text(tr("passphrase-load")).size(self.scaled(10.0)))
+                        .on_press(Message::PassphraseFilePick)
+                        .style(button::secondary),
+                ]
+                .push(self.passphrase_file.as_ref().map(|name| {
+                    text(tr_args(
+                        "passphrase-loaded",
+                        &fluent_args!["name" => name.as_str()],
+                    ))
+                    .size(self.scaled(10.0))
+                    .style(text::success)
+                }))
+                .spacing(10)
+                .align_y(iced::alignment::Vertical::Center),
+                passphrase_strength,
+                text(if self.passphrase_copied {
+                    tr("passphrase-copied")
+                } else {
+                    String::new()
+                })
+                .size(self.scaled(10.0))
+                .style(text::success),
+                passphrase_confirm,
+            ]
+            .spacing(10)
+        };
+        let inputs = column![
+            row![
+                text(tr("secret-label")).size(self.scaled(TEXT_SIZE)),
+                self.required_marker(),
+                horizontal_space(),
+            ]
+            .push(if self.is_file_secret {
+                toggler(self.separate_files)
+                    .label(tr("separate-files-toggle"))
+                    .on_toggle(Message::ToggleSeparateFiles)
+            } else {
+                toggler(self.batch)
+                    .label(tr("batch-toggle"))
+                    .on_toggle(Message::ToggleBatch)
+            })
+            .push(
+                toggler(self.is_file_secret)
+                    .label(tr("secret-file-toggle"))
+                    .on_toggle(Message::ToggleSecretSource),
+            )
+            .spacing(10),
+            secret_input,
+            self.secret_source(),
+            capacity_gauge,
+            row![
+                text(tr(if self.use_recipients {
+                    "recipients-label"
+                } else {
+                    "passphrase-label"
+                }))
+                .size(self.scaled(TEXT_SIZE)),
+                self.required_marker(),
+                horizontal_space(),
+                toggler(self.use_recipients)
+                    .label(tr("recipients-toggle"))
+                    .on_toggle(Message::ToggleRecipients),
+            ],
+            key_input,
+            self.warning(self.passphrase_warning.as_deref(), self.scaled(12.0)),
+        ]
+        .spacing(10);
+        let actions = container(
+            column![
+                qr_thumbnail,
+                row![
+                    button(text(tr("generate-pdf")))
+                        .on_press_maybe(self.can_generate().then_some(Message::GeneratePdf))
+                        .style(if self.is_generating {
+                            button::secondary
+                        } else {
+                            button::primary
+                        }),
+                    button(text(tr("check-inputs")))
+                        .on_press(Message::Validate)
+                        .style(button::secondary),
+                    button(text(tr("clear")))
+                        .on_press(Message::ClearSecrets)
+                        .style(button::danger),
+                ]
+                .spacing(10)
+                .align_y(iced::alignment::Vertical::Center),
+                text(if self.ready_to_generate {
+                    tr("ready-to-generate")
+                } else if !self.can_generate() {
+                    tr("required-hint")
+                } else {
+                    String::new()
+                })
+                .size(self.scaled(12.0))
+                .style(if self.ready_to_generate {
+                    text::success
+                } else {
+                    text::default
+                }),
+                row![
+                    button(text(tr("export-qr-png")).size(self.scaled(12.0)))
+                        .on_press(Message::ExportQrPng)
+                        .style(button::secondary),
+                    button(text(tr("save-age")).size(self.scaled(12.0)))
+                        .on_press(Message::ExportAge)
+                        .style(button::secondary),
+                    button(text(tr("encrypt-clipboard")).size(self.scaled(12.0)))
+                        .on_press(Message::EncryptClipboard)
+                        .style(button::secondary),
+                ]
+                .spacing(10),
+                idle_notice,
+            ]
+            .align_x(iced::alignment::Horizontal::Center),
+        )
+        .center_x(Fill);
+        let results = column![preview, fingerprint, batch_summary, saved].spacing(10);
+        let help = column![App::spoiler_button(
+            tr("help"),
+            self.show_help,
+            Message::ToggleHelp
+        )]
+        .push(self.show_help.then(|| self.help_view()))
+        .spacing(10);
+        if self.is_wide() {
+            row![
+                column![inputs, actions, results].spacing(10).width(Fill),
+                column![extra_config, help].spacing(10).width(Fill),
+            ]
+            .spacing(30)
+            .into()
+        } else {
+            column![inputs, extra_config, help, actions, results]
+                .spacing(10)
+                .into()
+        }
+    }
+
+    /// Generate the PDF step by step, reporting progress after each step. A
+    /// `payload` from an earlier run with the same inputs is used instead of
+    /// encrypting again.
+    fn generate_pdf(
+        layout: pdf::Layout,
+        secret: Secret,
+        key: KeyInput,
+        payload: Option<ArcStr>,
+        verify: bool,
+        revision: u64,
+    ) -> impl Stream<Item = Message> {
+        iced::stream::channel(pdf::STEPS, async move |mut output| {
+            let result = App::build_pdf(
+                layout,
+                secret,
+                key,
+                payload,
+                verify,
+                revision,
+                output.clone(),
+            )
+            .await;
+            for message in result {
+                let _ = output.send(message).await;
+            }
+        })
+    }
+
+    async fn build_pdf(
+        layout: pdf::Layout,
+        secret: Secret,
+        key: KeyInput,
+        payload: Option<ArcStr>,
+        verify: bool,
+        revision: u64,
+        mut progress: mpsc::Sender<Message>,
+    ) -> Vec<Message> {
+        let (secret, encryption) = match App::validate_key(secret, key) {
+            Ok(inputs) => inputs,
+            Err(warnings) => return warnings,
+        };
+        let passphrase = match &encryption {
+            Encryption::Passphrase(passphrase) => Some(passphrase.clone()),
+            Encryption::Recipients(_) => None,
+        };
+        let sheet = spawn_blocking(move || {
+            let on_step = |step| {
+                let _ =
+                    progress.try_send(Message::GenerateProgress(step as f32 / pdf::STEPS as f32));
+            };
+            match payload {
+                Some(payload) => pdf::lay_out(layout, payload.to_string(), on_step),
+                None => pdf::create(layout, &secret, encryption, on_step),
+            }
+            .map(|sheet| {
+                let verified = verify
+                    .then(|| decrypt::verify(&sheet.pdf, &sheet.payload, &secret, passphrase));
+                (sheet, verified)
+            })
+        });
+        match sheet.await {
+            // A sheet that can't be read back must not end up on paper
+            Ok((_, Some(Err(err)))) => vec![
+                Message::GenerateWarning(tr("warning-verify-failed").into()),
+                Message::GenerateErrorDetails(err.to_string().into()),
+            ],
+            Ok((sheet, _)) => vec![
+                Message::PayloadReady(revision, sheet.payload.into()),
+                Message::FingerprintReady(sheet.fingerprint.into()),
+                Message::PreviewReady(sheet.pdf.into()),
+            ],
+            Err(err) => vec![
+                Message::GenerateWarning(App::describe_error(&err).into()),
+                Message::GenerateErrorDetails(err.to_string().into()),
+            ],
+        }
+    }
+
+    /// Check the inputs before encrypting, returning the secret bytes and passphrase
+    /// or the warnings to show next to the offending fields
+    fn validate(
+        secret: Secret,
+        passphrase: &dyn PassphraseSource,
+    ) -> Result<(SecretBytes, SecretString), Vec<Message>> {
+        App::both(
+            App::validate_secret(secret),
+            App::validate_passphrase(passphrase),
+        )
+    }
+
+    /// Like [`App::validate`], for either kind of key material
+    fn validate_key(
+        secret: Secret,
+        key: KeyInput,
+    ) -> Result<(SecretBytes, Encryption), Vec<Message>> {
+        match key {
+            KeyInput::Passphrase(source) => App::validate(secret, &*source)
+                .map(|(secret, passphrase)| (secret, Encryption::Passphrase(passphrase))),
+            KeyInput::Recipients(recipients) => App::both(
+                App::validate_secret(secret),
+                App::validate_recipients(&recipients).map(Encryption::Recipients),
+            ),
+        }
+    }
+
+    /// Collect both results, keeping the warnings of both in order
+    fn both<A, B>(a: Result<A, Message>, b: Result<B, Message>) -> Result<(A, B), Vec<Message>> {
+        match (a, b) {
+            (Ok(a), Ok(b)) => Ok((a, b)),
+            (Err(e1), Ok(_)) => Err(vec![e1]),
+            (Ok(_), Err(e2)) => Err(vec![e2]),
+            (Err(e1), Err(e2)) => Err(vec![e1, e2]),
+        }
+    }
+
+    fn validate_secret(secret: Secret) -> Result<SecretBytes, Message> {
+        match secret {
+            Secret::Text(text, encoding) => encoding
+                .encode(&text)
+                .map(Arc::new)
+                .map_err(|c| App::unencodable_warning(encoding, c)),
+            Secret::Files(files, format) => match files.as_slice() {
+                [] => Err(ArcStr::from(tr("warning-select-file"))),
+                [(_, secret_bytes)] => Ok(secret_bytes.clone()),
+                files => format.pack(files).map(Arc::new).map_err(|err| {
+                    tr_args(
+                        "warning-archive-failed",
+                        &fluent_args!["error" => err.to_string()],
+                    )
+                    .into()
+                }),
+            },
+        }
+        .and_then(|secret_bytes| {
+            if secret_bytes.is_empty() {
+                Err(ArcStr::from(tr("warning-secret-empty")))
+            } else {
+                Ok(secret_bytes)
+            }
+        })
+        .map_err(Message::SecretWarning)
+    }
+
+    fn validate_passphrase(source: &dyn PassphraseSource) -> Result<SecretString, Message> {
+        source.passphrase().map_err(|err| {
+            Message::PassphraseWarning(
+                tr(match err {
+                    PassphraseError::Empty => "warning-passphrase-empty",
+                    PassphraseError::Mismatch => "warning-passphrase-mismatch",
+                })
+                .into(),
+            )
+        })
+    }
+
+    /// Parse `age1...` public keys separated by whitespace or commas
+    fn validate_recipients(recipients: &str) -> Result<Vec<x25519::Recipient>, Message> {
+        let recipients = recipients
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|key| !key.is_empty())
+            .map(|key| {
+                x25519::Recipient::from_str(key).map_err(|err| {
+                    tr_args(
+                        "warning-recipient-invalid",
+                        &fluent_args!["key" => key, "error" => err],
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, _>>();
+        match recipients {
+            Ok(recipients) if recipients.is_empty() => Err(tr("warning-recipients-empty")),
+            recipients => recipients,
         }
+        .map_err(ArcStr::from)
+        .map_err(Message::PassphraseWarning)
     }
 
-    fn view(&self) -> Element<'_, Message> {
-        let logo = svg(Handle::from_memory(Assets.logo().content()))
-            .height(Length::Fixed(100.0))
-            .style(|theme: &Theme, _| svg::Style {
-                color: Some(theme.palette().text),
-            });
-        let extra_arrow_icon = if self.show_extra {
-            svg(Handle::from_memory(
-                Assets.icons().arrow_drop_down_line().content(),
-            ))
-        } else {
-            svg(Handle::from_memory(
-                Assets.icons().arrow_drop_right_line().content(),
-            ))
+    async fn generate_qr_png(
+        secret: Secret,
+        key: KeyInput,
+        ecc_level: EccLevel,
+        armor: bool,
+        binary_qr: bool,
+    ) -> Vec<Message> {
+        let (secret, encryption) = match App::validate_key(secret, key) {
+            Ok(inputs) => inputs,
+            Err(warnings) => return warnings,
+        };
+        let png = spawn_blocking(move || {
+            let mut encrypted = pdf::encrypt(&secret, encryption)?;
+            if !armor || binary_qr {
+                encrypted = pdf::compact(&encrypted);
+            }
+            let data = pdf::qr_data(&encrypted, binary_qr)?;
+            pdf::check_ecc(&data, ecc_level)?;
+            export::qr_png(&data)
+        });
+        match png.await {
+            Ok(png) => vec![Message::SaveQrPng(png.into())],
+            Err(err) => vec![
+                Message::GenerateWarning(tr("error-qr-image").into()),
+                Message::GenerateErrorDetails(err.to_string().into()),
+            ],
         }
-        .height(Length::Fixed(24.0))
-        .width(Length::Fixed(12.0))
-        .style(|theme: &Theme, _| svg::Style {
-            color: Some(theme.palette().background),
+    }
+
+    /// Encrypt once and render a small QR code of the result, the thumbnail
+    /// being left out if the code doesn't fit. Nothing while the inputs are invalid.
+    async fn encrypt_preview(
+        secret: Secret,
+        key: KeyInput,
+        ecc_level: EccLevel,
+        armor: bool,
+        binary_qr: bool,
+    ) -> Option<(ArcStr, Option<ArcBytes>)> {
+        let (secret, encryption) = App::validate_key(secret, key).ok()?;
+        spawn_blocking(move || {
+            let mut encrypted = pdf::encrypt(&secret, encryption).ok()?;
+            if !armor || binary_qr {
+                encrypted = pdf::compact(&encrypted);
+            }
+            let png = pdf::qr_data(&encrypted, binary_qr)
+                .ok()
+                .filter(|data| pdf::check_ecc(data, ecc_level).is_ok())
+                .and_then(|data| export::qr_thumbnail(&data).ok())
+                .map(ArcBytes::from);
+            Some((encrypted.into(), png))
         })
-        .content_fit(iced::ContentFit::ScaleDown);
-        let extra_button =
-            button(row![extra_arrow_icon, "Extra"].align_y(iced::alignment::Vertical::Center))
-                .on_press(Message::ToggleExtraSpoiler);
-        let extra_config = if self.show_extra {
-            column![
-                extra_button,
-                text("Title:"),
-                text_input("PaperAge", &self.title).on_input(Message::TitleChanged),
-                text("Notes Label:"),
-                text_input("Notes Label", &self.notes_label).on_input(Message::NotesLabelChanged),
-                text("Page Size:"),
-                pick_list(
-                    [PageSize::A4, PageSize::Letter,],
-                    Some(self.page_size.clone()),
-                    Message::PageSizeChanged,
-                ),
-            ]
-        } else {
-            column![extra_button,]
+        .await
+    }
+
+    /// Generate and save one PDF per sheet, collecting failures instead of
+    /// stopping at the first one. The whole `secret` is only validated, so that
+    /// an empty input is reported like when generating a single sheet.
+    async fn generate_batch(
+        folder: BatchFolder,
+        layout: pdf::Layout,
+        secret: Secret,
+        sheets: Vec<(Option<String>, SecretBytes)>,
+        key: KeyInput,
+    ) -> Vec<Message> {
+        let (_, encryption) = match App::validate_key(secret, key) {
+            Ok(inputs) => inputs,
+            Err(warnings) => return warnings,
         };
-        let secret_input = if self.is_file_secret {
-            column![
-                row![
-                    button("Open").on_press(Message::SecretFilePick).style(
-                        if self.secret_file_loading {
-                            button::secondary
-                        } else {
-                            button::primary
-                        }
-                    ),
-                    container(
-                        text(
-                            self.secret_file_name
-                                .as_ref()
-                                .map(ArcStr::as_str)
-                                .unwrap_or_default()
-                        )
-                        .width(Length::Fill)
-                    )
-                    .padding(15),
-                ]
-                .align_y(iced::alignment::Vertical::Center),
-                text(
-                    self.secret_warning
-                        .as_ref()
-                        .map(ArcStr::as_str)
-                        .unwrap_or_default()
-                )
-                .size(10)
-                .style(text::danger),
-            ]
+        let mut outcomes = vec![];
+        for (index, (title, secret)) in sheets.into_iter().enumerate() {
+            let layout = pdf::Layout {
+                title: title.unwrap_or_else(|| layout.title.clone()),
+                ..layout.clone()
+            };
+            let title = if layout.title.is_empty() {
+                pdf::DEFAULT_TITLE
+            } else {
+                &layout.title
+            };
+            let file_name = format!("{}-{}.pdf", index + 1, file_slug(title));
+            let label = ArcStr::from(format!("{}. {title}", index + 1));
+            let encryption = encryption.clone();
+            let sheet =
+                spawn_blocking(move || pdf::create(layout, &secret, encryption, |_| {})).await;
+            let saved_to = App::save_batch_sheet(&folder, sheet, file_name).await;
+            outcomes.push(BatchOutcome { label, saved_to });
+        }
+        vec![Message::BatchDone(outcomes)]
+    }
+
+    /// Encrypt once and spread the ciphertext over as many sheets as it needs,
+    /// see [`split`]
+    async fn generate_split(
+        folder: BatchFolder,
+        layout: pdf::Layout,
+        secret: Secret,
+        key: KeyInput,
+    ) -> Vec<Message> {
+        let (secret, encryption) = match App::validate_key(secret, key) {
+            Ok(inputs) => inputs,
+            Err(warnings) => return warnings,
+        };
+        let encrypted = match spawn_blocking(move || pdf::encrypt(&secret, encryption)).await {
+            Ok(encrypted) => encrypted,
+            Err(err) => {
+                return vec![
+                    Message::GenerateWarning(App::describe_error(&err).into()),
+                    Message::GenerateErrorDetails(err.to_string().into()),
+                ];
+            }
+        };
+        let parts = split::split(
+            &encrypted,
+            capacity::qr_capacity(&layout.page_size, layout.min_ecc),
+        );
+        let count = parts.len();
+        let title = if layout.title.is_empty() {
+            pdf::DEFAULT_TITLE.to_string()
         } else {
-            column![
-                text_editor(&self.secret_content).on_action(Message::SecretContentChanged),
-                text(
-                    self.secret_warning
-                        .as_ref()
-                        .map(ArcStr::as_str)
-                        .unwrap_or_default()
-                )
-                .size(10)
-                .style(text::danger),
-            ]
+            layout.title.clone()
         };
-        scrollable(
-            container(
-                container(
-                    column![
-                        logo,
-                        container(text("Paper Age").size(35)).center_x(Fill),
-                        row![
-                            text("Secret:"),
-                            horizontal_space(),
-                            toggler(self.is_file_secret)
-                                .label("File")
-                                .on_toggle(Message::ToggleSecretSource),
-                        ],
-                        secret_input,
-                        text("Passphrase:"),
-                        text_input("Passphrase", self.passphrase.expose_secret())
-                            .on_input(Message::PassphraseChanged)
-                            .secure(true),
-                        text(
-                            self.passphrase_warning
-                                .as_ref()
-                                .map(ArcStr::as_str)
-                                .unwrap_or_default()
-                        )
-                        .size(10)
-                        .style(text::danger),
-                        extra_config,
-                        container(
-                            column![
-                                button("Generate PDF").on_press(Message::GeneratePdf).style(
-                                    if self.is_generating {
-                                        button::secondary
-                                    } else {
-                                        button::primary
-                                    }
-                                ),
-                                text(
-                                    self.generate_warning
-                                        .as_ref()
-                                        .map(ArcStr::as_str)
-                                        .unwrap_or_default()
-                                )
-                                .size(10)
-                                .style(text::danger),
-                            ]
-                            .align_x(iced::alignment::Horizontal::Center)
-                        )
-                        .center_x(Fill),
-                    ]
-                    .spacing(10),
-                )
-                .max_width(400),
-            )
-            .padding(30)
-            .center_x(Fill),
-        )
-        .into()
+        let mut outcomes = vec![];
+        for (index, part) in parts.into_iter().enumerate() {
+            let label = tr_args(
+                "split-sheet-title",
+                &fluent_args!["title" => title.as_str(), "part" => index + 1, "count" => count],
+            );
+            let layout = pdf::Layout {
+                title: label.clone(),
+                // Parts are cut at the line breaks of the armor and carry a
+                // header, so they stay text
+                binary_qr: false,
+                ..layout.clone()
+            };
+            let file_name = format!("{}-{}-of-{count}.pdf", file_slug(&title), index + 1);
+            let sheet = spawn_blocking(move || pdf::lay_out(layout, part, |_| {})).await;
+            let saved_to = App::save_batch_sheet(&folder, sheet, file_name).await;
+            outcomes.push(BatchOutcome {
+                label: label.into(),
+                saved_to,
+            });
+        }
+        vec![Message::BatchDone(outcomes)]
+    }
+
+    /// Save one sheet of a batch or split, returning where it went or what failed
+    async fn save_batch_sheet(
+        folder: &BatchFolder,
+        sheet: Result<pdf::Sheet, PaperAgeError>,
+        file_name: String,
+    ) -> Result<ArcStr, ArcStr> {
+        match sheet {
+            Ok(sheet) => match App::save_batch_pdf(folder, sheet.pdf.into(), file_name).await {
+                Ok(Some(path)) => Ok(path),
+                Ok(None) => Err(tr("batch-not-saved").into()),
+                Err(err) => Err(err.to_string().into()),
+            },
+            Err(err) => Err(App::describe_error(&err).into()),
+        }
+    }
+
+    async fn generate_age(secret: Secret, key: KeyInput) -> Vec<Message> {
+        let (secret, encryption) = match App::validate_key(secret, key) {
+            Ok(inputs) => inputs,
+            Err(warnings) => return warnings,
+        };
+        match spawn_blocking(move || pdf::encrypt(&secret, encryption)).await {
+            Ok(armored) => vec![Message::SaveAge(armored.into())],
+            Err(err) => vec![
+                Message::GenerateWarning(App::describe_error(&err).into()),
+                Message::GenerateErrorDetails(err.to_string().into()),
+            ],
+        }
     }
 
-    async fn generate_pdf(
-        title: ArcStr,
-        notes_label: ArcStr,
-        page_size: PageSize,
-        secret: Option<ArcBytes>,
+    /// Read the QR codes of the PDFs and decrypt them, putting the parts of a
+    /// split secret back together first
+    async fn decrypt_pdfs(
+        pdfs: Vec<ArcBytes>,
         passphrase: SecretString,
-    ) -> Vec<Message> {
-        let secret_res = match secret {
-            Some(secret_bytes) => {
-                if secret_bytes.is_empty() {
-                    Err("Secret is empty")
-                } else {
-                    Ok(secret_bytes)
-                }
+    ) -> Result<SecretBytes, ArcStr> {
+        spawn_blocking(move || {
+            let payloads = pdfs
+                .iter()
+                .map(|pdf| decrypt::read_payload(pdf))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| ArcStr::from(tr("warning-no-qr-code")))?;
+            let payload = match payloads.as_slice() {
+                [payload] if !split::is_part(payload) => payload.clone(),
+                parts if parts.iter().all(|part| split::is_part(part)) => split::join(parts)
+                    .map_err(|err| match err {
+                        split::JoinError::Missing(missing) => tr_args(
+                            "warning-parts-missing",
+                            &fluent_args![
+                                "parts" => missing
+                                    .iter()
+                                    .map(usize::to_string)
+                                    .collect::<Vec<_>>()
+                                    .join(", "),
+                            ],
+                        ),
+                        split::JoinError::Mixed => tr("warning-parts-mixed"),
+                    })?,
+                _ => return Err(tr("warning-several-pdfs").into()),
+            };
+            decrypt::decrypt(&payload, passphrase)
+                .map(Arc::new)
+                .map_err(|err| {
+                    tr_args(
+                        "warning-decryption-failed",
+                        &fluent_args!["error" => err.to_string()],
+                    )
+                    .into()
+                })
+        })
+        .await
+    }
+
+    fn describe_error(err: &PaperAgeError) -> String {
+        tr(match err {
+            PaperAgeError::Encryption(_) => "error-encryption",
+            PaperAgeError::DocumentInit(_) => "error-document-init",
+            PaperAgeError::PdfCreation(msg) if msg.contains("error correction") => {
+                "error-ecc-too-high"
+            }
+            PaperAgeError::PdfCreation(msg) if msg.contains("data too long") => {
+                "error-data-too-long"
             }
-            None => Err("Select file"),
+            PaperAgeError::PdfCreation(_) => "error-pdf-creation",
+        })
+    }
+
+    async fn render_preview(pdf: ArcBytes) -> Option<image::Handle> {
+        spawn_blocking(move || {
+            let pdf = hayro::hayro_syntax::Pdf::new(pdf.to_vec()).ok()?;
+            // The sheet comes after the instructions page if there is one
+            let page = pdf.pages().last()?;
+            let pixmap = hayro::render(
+                page,
+                &hayro::RenderCache::new(),
+                &Default::default(),
+                &Default::default(),
+                &hayro::PixmapSettings {
+                    x_scale: 1.0,
+                    y_scale: 1.0,
+                    bg_color: hayro::vello_cpu::color::palette::css::WHITE,
+                },
+            );
+            Some(image::Handle::from_rgba(
+                pixmap.width().into(),
+                pixmap.height().into(),
+                pixmap.data_as_u8_slice().to_vec(),
+            ))
+        })
+        .await
+    }
+
+    /// Read a secret file, refusing files that can never fit, not even split
+    /// across sheets
+    async fn read_secret_file(file: FileHandle) -> Result<(ArcStr, SecretBytes), ArcStr> {
+        let name = ArcStr::from(file.file_name());
+        let too_large = || {
+            ArcStr::from(tr_args(
+                "warning-file-too-large",
+                &fluent_args!["name" => name.as_str(), "limit" => capacity::MAX_FILE_SIZE],
+            ))
+        };
+        // Check the size up front where possible so huge files are never read
+        #[cfg(not(target_arch = "wasm32"))]
+        if std::fs::metadata(file.path()).is_ok_and(|m| m.len() > capacity::MAX_FILE_SIZE as u64) {
+            return Err(too_large());
         }
-        .map_err(ArcStr::from)
-        .map_err(Message::SecretWarning);
-        let passphrase_res = if passphrase.expose_secret().is_empty() {
-            Err("Passphrase is empty")
-        } else {
-            Ok(passphrase.clone())
+        let content = Zeroizing::new(file.read().await);
+        if content.len() > capacity::MAX_FILE_SIZE {
+            return Err(too_large());
         }
-        .map_err(ArcStr::from)
-        .map_err(Message::PassphraseWarning);
-        let (secret, passphrase) = match (secret_res, passphrase_res) {
-            (Ok(secret), Ok(passphrase)) => (secret, passphrase),
-            (Err(e1), Ok(_)) => return vec![e1],
-            (Ok(_), Err(e2)) => return vec![e2],
-            (Err(e1), Err(e2)) => return vec![e1, e2],
-        };
-        let mut secret_reader = Cursor::new(secret);
-        let pdf = match create_pdf(
-            if title.is_empty() {
-                "PaperAge".to_string()
-            } else {
-                title.to_string()
-            },
-            &mut secret_reader,
-            passphrase.expose_secret(),
-            Some(if notes_label.is_empty() {
-                "Passphrase:".to_string()
+        Ok((name.clone(), Arc::new(content)))
+    }
+
+    /// Download a secret file, refusing content that can never fit like
+    /// [`App::read_secret_file`]. Credentials in the address are sent as basic
+    /// authentication.
+    #[cfg(feature = "url")]
+    async fn read_secret_url(url: String) -> Result<(ArcStr, SecretBytes), ArcStr> {
+        let failed = |err: reqwest::Error| -> ArcStr {
+            if err.is_timeout() {
+                tr("warning-url-timeout").into()
             } else {
-                notes_label.to_string()
-            }),
-            Some(false),
-            Some(page_size),
-            Some(false),
-        ) {
-            Ok(content) => content,
-            Err(err) => return vec![Message::GenerateWarning(format!("Error: {}", err).into())],
+                // The cause, like a refused connection, is only in the source chain
+                let error = format!("{:#}", anyhow::Error::from(err.without_url()));
+                tr_args("warning-url-failed", &fluent_args!["error" => error]).into()
+            }
+        };
+        let client = reqwest::Client::builder()
+            .timeout(URL_TIMEOUT)
+            .build()
+            .map_err(failed)?;
+        let mut response = client.get(url).send().await.map_err(failed)?;
+        if response.status() != reqwest::StatusCode::OK {
+            return Err(tr_args(
+                "warning-url-status",
+                &fluent_args!["status" => response.status().to_string()],
+            )
+            .into());
+        }
+        let name = ArcStr::from(
+            response
+                .url()
+                .path_segments()
+                .and_then(|mut segments| segments.next_back())
+                .filter(|name| !name.is_empty())
+                .unwrap_or("download"),
+        );
+        // Allocated at the largest accepted size, so it never grows and
+        // leaves copies of the secret behind
+        let mut content = Zeroizing::new(Vec::with_capacity(capacity::MAX_FILE_SIZE));
+        while let Some(chunk) = response.chunk().await.map_err(failed)? {
+            if content.len() + chunk.len() > capacity::MAX_FILE_SIZE {
+                return Err(tr_args(
+                    "warning-file-too-large",
+                    &fluent_args!["name" => name.as_str(), "limit" => capacity::MAX_FILE_SIZE],
+                )
+                .into());
+            }
+            content.extend_from_slice(&chunk);
+        }
+        Ok((name, Arc::new(content)))
+    }
+
+    /// Read and parse a font file, which takes a moment for large CJK fonts
+    async fn read_font(file: FileHandle) -> Result<(ArcStr, pdf::Font), ArcStr> {
+        let name = ArcStr::from(file.file_name());
+        let content = file.read().await;
+        spawn_blocking(move || pdf::Font::parse(&content))
+            .await
+            .map(|font| (name.clone(), font))
+            .ok_or_else(|| {
+                tr_args(
+                    "warning-font-invalid",
+                    &fluent_args!["name" => name.as_str()],
+                )
+                .into()
+            })
+    }
+
+    async fn read_passphrase_file(file: FileHandle) -> Result<(ArcStr, SecretString), ArcStr> {
+        let name = ArcStr::from(file.file_name());
+        parse_passphrase_file(file.read().await)
+            .map(|passphrase| (name, passphrase))
+            .map_err(ArcStr::from)
+    }
+
+    async fn pick_secret(dialog: rfd::AsyncFileDialog) -> Option<Vec<FileHandle>> {
+        dialog.pick_files().await
+    }
+
+    /// File dialog starting in the folder the user last saved to
+    fn file_dialog(&self) -> rfd::AsyncFileDialog {
+        let dialog = rfd::AsyncFileDialog::new();
+        match &self.last_dir {
+            Some(dir) => dialog.set_directory(dir),
+            None => dialog,
+        }
+    }
+
+    /// File name suggested from the title, e.g. "SSH Key" becomes `ssh-key.pdf`
+    fn default_file_name(&self) -> String {
+        format!("{}.pdf", file_slug(&self.title))
+    }
+
+    /// Output file name with the given extension, replacing a `.pdf` one from the override
+    fn output_file_name(&self, extension: &str) -> String {
+        let name = match &self.output_name {
+            Some(name) => name.to_string(),
+            None => self.default_file_name(),
+        };
+        let stem = match name.rsplit_once('.') {
+            Some((stem, ext)) if ext.eq_ignore_ascii_case("pdf") => stem,
+            _ => &name,
         };
-        vec![Message::SaveSecretPdf(pdf.into())]
+        format!("{stem}.{extension}")
+    }
+
+    /// Save the PDF and return where it went, or `None` if the dialog was cancelled
+    async fn save_pdf(
+        dialog: rfd::AsyncFileDialog,
+        content: ArcBytes,
+        file_name: String,
+    ) -> Result<Option<ArcStr>> {
+        Self::save_file(dialog, content, file_name, "PDF", "pdf", "application/pdf").await
+    }
+
+    async fn save_png(
+        dialog: rfd::AsyncFileDialog,
+        content: ArcBytes,
+        file_name: String,
+    ) -> Result<Option<ArcStr>> {
+        Self::save_file(dialog, content, file_name, "PNG", "png", "image/png").await
+    }
+
+    async fn save_age(
+        dialog: rfd::AsyncFileDialog,
+        content: ArcStr,
+        file_name: String,
+    ) -> Result<Option<ArcStr>> {
+        let content = content.as_bytes().into();
+        Self::save_file(dialog, content, file_name, "age", "age", "text/plain").await
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn pick_batch_folder(dialog: rfd::AsyncFileDialog) -> Option<BatchFolder> {
+        dialog
+            .pick_folder()
+            .await
+            .map(|folder| folder.path().to_path_buf())
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    async fn pick_batch_folder(_dialog: rfd::AsyncFileDialog) -> Option<BatchFolder> {
+        Some(())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn save_batch_pdf(
+        folder: &BatchFolder,
+        content: ArcBytes,
+        file_name: String,
+    ) -> Result<Option<ArcStr>> {
+        let path = folder.join(file_name);
+        spawn_blocking(move || {
+            std::fs::write(&path, content)?;
+            Ok(Some(path.display().to_string().into()))
+        })
+        .await
     }
 
-    async fn pick_secret() -> Option<FileHandle> {
-        rfd::AsyncFileDialog::new().pick_file().await //.map(Mutex::new).map(Arc::new)
+    #[cfg(target_arch = "wasm32")]
+    async fn save_batch_pdf(
+        _folder: &BatchFolder,
+        content: ArcBytes,
+        file_name: String,
+    ) -> Result<Option<ArcStr>> {
+        Self::save_pdf(rfd::AsyncFileDialog::new(), content, file_name).await
     }
 
-    async fn save_pdf(content: ArcBytes) -> Result<()> {
-        if let Some(file) = rfd::AsyncFileDialog::new()
-            .add_filter("PDF", &["pdf"])
-            .set_file_name("secret.pdf")
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn save_file(
+        dialog: rfd::AsyncFileDialog,
+        content: ArcBytes,
+        file_name: String,
+        filter: &str,
+        extension: &str,
+        _mime: &str,
+    ) -> Result<Option<ArcStr>> {
+        let Some(file) = dialog
+            .add_filter(filter, &[extension])
+            .set_file_name(file_name)
             .save_file()
             .await
-        {
-            file.write(&content).await?
+        else {
+            return Ok(None);
         };
-        Ok(())
+        file.write(&content).await?;
+        Ok(Some(file.path().display().to_string().into()))
+    }
+
+    /// Browsers don't reliably start a download from `rfd`, so files are
+    /// handed over through an `<a download>` link instead
+    #[cfg(target_arch = "wasm32")]
+    async fn save_file(
+        _dialog: rfd::AsyncFileDialog,
+        content: ArcBytes,
+        file_name: String,
+        _filter: &str,
+        _extension: &str,
+        mime: &str,
+    ) -> Result<Option<ArcStr>> {
+        download(&content, &file_name, mime)?;
+        Ok(Some(file_name.into()))
+    }
+}
+
+/// Offer `content` as a browser download named `file_name`
+#[cfg(target_arch = "wasm32")]
+fn download(content: &[u8], file_name: &str, mime: &str) -> Result<()> {
+    use anyhow::Context;
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen::closure::Closure;
+
+    let window = web_sys::window().context("No window")?;
+    let document = window.document().context("No document")?;
+    let url = blob_url(content, mime)?;
+    let link = document
+        .create_element("a")
+        .map_err(js_error)?
+        .unchecked_into::<web_sys::HtmlAnchorElement>();
+    link.set_href(&url);
+    link.set_download(file_name);
+    link.click();
+    // Some browsers only start reading the blob after the click returns, so
+    // the URL stays valid for a while
+    let revoke = Closure::once_into_js(move || {
+        let _ = web_sys::Url::revoke_object_url(&url);
+    });
+    window
+        .set_timeout_with_callback_and_timeout_and_arguments_0(
+            revoke.unchecked_ref(),
+            DOWNLOAD_REVOKE_DELAY_MS,
+        )
+        .map_err(js_error)?;
+    Ok(())
+}
+
+/// Show a saved PDF in a new tab. A download has no path to open, so the tab
+/// gets the same bytes and keeps their blob URL until the page is closed.
+#[cfg(target_arch = "wasm32")]
+fn open_saved(_path: &str, content: &[u8]) -> Result<()> {
+    use anyhow::Context;
+
+    let window = web_sys::window().context("No window")?;
+    let url = blob_url(content, "application/pdf")?;
+    window
+        .open_with_url_and_target(&url, "_blank")
+        .map_err(js_error)?;
+    Ok(())
+}
+
+/// Show a saved PDF in the default viewer
+#[cfg(not(target_arch = "wasm32"))]
+fn open_saved(path: &str, _content: &[u8]) -> Result<()> {
+    opener::open(path)?;
+    Ok(())
+}
+
+/// Open the print dialog for a PDF through a hidden frame, which shows the
+/// browser's own PDF viewer
+#[cfg(target_arch = "wasm32")]
+fn print(content: &[u8], _file_name: &str) -> Result<()> {
+    use anyhow::Context;
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen::closure::Closure;
+
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .context("No document")?;
+    let body = document.body().context("No document body")?;
+    let url = blob_url(content, "application/pdf")?;
+    let frame = document
+        .create_element("iframe")
+        .map_err(js_error)?
+        .unchecked_into::<web_sys::HtmlIFrameElement>();
+    // Some browsers print a blank page from frames that are not displayed
+    frame
+        .set_attribute("style", "position: fixed; width: 0; height: 0; border: 0")
+        .map_err(js_error)?;
+    let loaded = frame.clone();
+    let onload = Closure::once_into_js(move || {
+        if let Some(window) = loaded.content_window() {
+            let _ = window.print();
+        }
+    });
+    frame.set_onload(Some(onload.unchecked_ref()));
+    frame.set_src(&url);
+    body.append_child(&frame).map_err(js_error)?;
+    Ok(())
+}
+
+/// Hand a PDF to the printing system: `lp` on Linux and macOS, the print verb
+/// of the default PDF viewer on Windows
+#[cfg(not(target_arch = "wasm32"))]
+fn print(content: &[u8], file_name: &str) -> Result<()> {
+    use std::process::Command;
+
+    let path = std::env::temp_dir().join(file_name);
+    std::fs::write(&path, content)?;
+    #[cfg(windows)]
+    let status = Command::new("powershell")
+        .args(["-NoProfile", "-Command"])
+        .arg(format!(
+            "Start-Process -FilePath '{}' -Verb Print",
+            path.display().to_string().replace('\'', "''")
+        ))
+        .status();
+    #[cfg(not(windows))]
+    let status = Command::new("lp").arg(&path).status();
+    // `lp` keeps its own copy in the queue, but the viewer on Windows is still
+    // reading the file, which only holds ciphertext anyway
+    #[cfg(not(windows))]
+    let _ = std::fs::remove_file(&path);
+    let status = status?;
+    anyhow::ensure!(status.success(), "The print command failed with {status}");
+    Ok(())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn js_error(err: wasm_bindgen::JsValue) -> anyhow::Error {
+    anyhow::anyhow!("{err:?}")
+}
+
+/// Object URL for `content`, to be revoked once it is no longer needed
+#[cfg(target_arch = "wasm32")]
+fn blob_url(content: &[u8], mime: &str) -> Result<String> {
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(content));
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type(mime);
+    let blob = web_sys::Blob::new_with_u8_array_sequence_and_options(&parts, &options)
+        .map_err(js_error)?;
+    web_sys::Url::create_object_url_with_blob(&blob).map_err(js_error)
+}
+
+/// Paragraphs of the instructions page for a sheet, see [`pdf::Layout::instructions`]
+fn instructions(
+    notes_label: &str,
+    armor: bool,
+    binary_qr: bool,
+    archive: Option<ArchiveFormat>,
+) -> Vec<String> {
+    let notes_label = if notes_label.is_empty() {
+        pdf::DEFAULT_NOTES_LABEL
+    } else {
+        notes_label
+    };
+    let mut paragraphs = vec![
+        tr("instructions-title"),
+        tr_args("instructions-intro", &fluent_args!["notes" => notes_label]),
+        tr("instructions-scan"),
+    ];
+    if !armor || binary_qr {
+        paragraphs.push(tr("instructions-compact"));
+    }
+    if binary_qr {
+        paragraphs.push(tr("instructions-binary"));
+    }
+    paragraphs.push(tr("instructions-decrypt"));
+    if let Some(format) = archive {
+        paragraphs.push(tr_args(
+            "instructions-archive",
+            &fluent_args!["format" => format.to_string()],
+        ));
+    }
+    paragraphs.push(tr("instructions-app"));
+    paragraphs
+}
+
+/// The first `max` characters of `value`
+fn truncate(value: &str, max: usize) -> &str {
+    value
+        .char_indices()
+        .nth(max)
+        .map_or(value, |(index, _)| &value[..index])
+}
+
+/// Convert CRLF line endings to LF. Binary content is left alone, as there a
+/// CRLF pair is data rather than a line ending.
+fn normalize_newlines(content: &SecretBytes) -> SecretBytes {
+    match std::str::from_utf8(content) {
+        Ok(text) if text.contains("\r\n") => secret_bytes(text.replace("\r\n", "\n").into_bytes()),
+        _ => content.clone(),
+    }
+}
+
+/// Passphrase from a single-line file, wiping the raw bytes afterwards
+fn parse_passphrase_file(mut content: Vec<u8>) -> Result<SecretString, String> {
+    let passphrase = match std::str::from_utf8(&content) {
+        Ok(text) => {
+            let line = text.trim_end_matches(['\r', '\n']);
+            if line.is_empty() {
+                Err(tr("warning-passphrase-file-empty"))
+            } else if line.contains('\n') {
+                Err(tr("warning-passphrase-file-lines"))
+            } else {
+                Ok(SecretString::from(line))
+            }
+        }
+        Err(_) => Err(tr("warning-passphrase-file-binary")),
+    };
+    content.zeroize();
+    passphrase
+}
+
+/// Lowercase words of `title` joined by dashes, for use in file names
+fn file_slug(title: &str) -> String {
+    let slug = title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() {
+        "secret".to_string()
+    } else {
+        slug
     }
 }
 
+/// Run CPU-bound work without blocking the UI executor
+#[cfg(not(target_arch = "wasm32"))]
+async fn spawn_blocking<T, F>(f: F) -> T
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .unwrap_or_else(|err| std::panic::resume_unwind(err.into_panic()))
+}
+
+/// There are no threads on the web, so the work runs in place
+#[cfg(target_arch = "wasm32")]
+async fn spawn_blocking<T, F>(f: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    f()
+}
+
+/// Whether the data starts like an armored or binary age file
+fn is_age_file(data: &[u8]) -> bool {
+    data.starts_with(b"-----BEGIN AGE ENCRYPTED FILE-----")
+        || data.starts_with(b"age-encryption.org/")
+}
+
+/// Whether editor text looks like pasted binary data, which does not survive the
+/// round trip through a UTF-8 string
+fn looks_binary(text: &str) -> bool {
+    text.chars().any(|c| {
+        c == char::REPLACEMENT_CHARACTER || (c.is_control() && !matches!(c, '\t' | '\n' | '\r'))
+    })
+}
+
 fn horizontal_space() -> Space {
     Space::new().width(Length::Fill)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(secret: &str) -> Secret {
+        Secret::Text(Zeroizing::new(secret.to_string()), TextEncoding::Utf8)
+    }
+
+    fn file(content: &[u8]) -> Secret {
+        Secret::Files(
+            vec![("secret.txt".into(), secret_bytes(content.to_vec()))],
+            ArchiveFormat::Tar,
+        )
+    }
+
+    fn passphrase(passphrase: &str) -> Manual {
+        Manual {
+            passphrase: passphrase.into(),
+            confirm: None,
+        }
+    }
+
+    /// The field of every warning with its text, in order
+    fn warnings<T: std::fmt::Debug>(
+        result: Result<T, Vec<Message>>,
+    ) -> Vec<(&'static str, String)> {
+        result
+            .unwrap_err()
+            .into_iter()
+            .map(|message| match message {
+                Message::SecretWarning(warning) => ("secret", warning.to_string()),
+                Message::PassphraseWarning(warning) => ("passphrase", warning.to_string()),
+                message => panic!("Not a warning: {message:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn validate_valid_inputs() {
+        let (secret, passphrase) =
+            App::validate(text("secret"), &passphrase("correct horse")).unwrap();
+        assert_eq!(**secret, b"secret");
+        assert_eq!(passphrase.expose_secret(), "correct horse");
+    }
+
+    #[test]
+    fn validate_valid_file() {
+        let (secret, _) = App::validate(file(b"\x00\xff"), &passphrase("correct horse")).unwrap();
+        assert_eq!(**secret, b"\x00\xff");
+    }
+
+    #[test]
+    fn validate_empty_secret() {
+        assert_eq!(
+            warnings(App::validate(text(""), &passphrase("correct horse"))),
+            [("secret", tr("warning-secret-empty"))]
+        );
+    }
+
+    #[test]
+    fn validate_empty_file() {
+        assert_eq!(
+            warnings(App::validate(file(b""), &passphrase("correct horse"))),
+            [("secret", tr("warning-secret-empty"))]
+        );
+    }
+
+    #[test]
+    fn validate_no_file_selected() {
+        assert_eq!(
+            warnings(App::validate(
+                Secret::Files(vec![], ArchiveFormat::Tar),
+                &passphrase("correct horse")
+            )),
+            [("secret", tr("warning-select-file"))]
+        );
+    }
+
+    #[test]
+    fn validate_unencodable_secret() {
+        let secret = Secret::Text(Zeroizing::new("✓".to_string()), TextEncoding::Latin1);
+        let warnings = warnings(App::validate(secret, &passphrase("correct horse")));
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].0, "secret");
+    }
+
+    #[test]
+    fn validate_empty_passphrase() {
+        assert_eq!(
+            warnings(App::validate(text("secret"), &passphrase(""))),
+            [("passphrase", tr("warning-passphrase-empty"))]
+        );
+    }
+
+    #[test]
+    fn validate_mismatched_confirmation() {
+        let source = Manual {
+            passphrase: "correct horse".into(),
+            confirm: Some("correct house".into()),
+        };
+        assert_eq!(
+            warnings(App::validate(text("secret"), &source)),
+            [("passphrase", tr("warning-passphrase-mismatch"))]
+        );
+    }
+
+    #[test]
+    fn validate_both_empty_in_order() {
+        assert_eq!(
+            warnings(App::validate(text(""), &passphrase(""))),
+            [
+                ("secret", tr("warning-secret-empty")),
+                ("passphrase", tr("warning-passphrase-empty")),
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_key_without_recipients() {
+        assert_eq!(
+            warnings(App::validate_key(
+                Secret::Files(vec![], ArchiveFormat::Tar),
+                KeyInput::Recipients("".into())
+            )),
+            [
+                ("secret", tr("warning-select-file")),
+                ("passphrase", tr("warning-recipients-empty")),
+            ]
+        );
+    }
+}
@@ -0,0 +1,146 @@
+//! Where the passphrase for encrypting comes from.
+//!
+//! Only passphrases typed into the form exist so far. Other sources, such as
+//! one unlocked by a hardware security key, plug in by implementing
+//! [`PassphraseSource`] and are then validated and used like a typed one.
+
+use age::secrecy::{ExposeSecret, SecretString};
+use std::collections::HashSet;
+use std::fmt;
+
+/// Shortest passphrase accepted without a warning
+const MIN_LENGTH: usize = 12;
+
+/// Fewest different characters accepted without a warning
+const MIN_DISTINCT: usize = 6;
+
+/// Lowercase letters alone make up for their small alphabet with this length
+const MIN_LOWERCASE_LENGTH: usize = 20;
+
+/// Why a source couldn't provide a passphrase
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PassphraseError {
+    Empty,
+    /// The confirmation differs from the passphrase
+    Mismatch,
+}
+
+/// What makes a passphrase easy to guess, each with its own remedy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Weakness {
+    /// Needs more characters
+    Short,
+    /// Needs a wider mix of characters
+    Uniform,
+}
+
+/// Simple rules a user can act on, unlike a strength score. Valid passphrases
+/// that break them are still accepted after a warning.
+pub fn weakness(passphrase: &str) -> Option<Weakness> {
+    let length = passphrase.chars().count();
+    let distinct = passphrase.chars().collect::<HashSet<_>>().len();
+    if length < MIN_LENGTH {
+        Some(Weakness::Short)
+    } else if distinct < MIN_DISTINCT
+        || (length < MIN_LOWERCASE_LENGTH && passphrase.chars().all(|c| c.is_ascii_lowercase()))
+    {
+        Some(Weakness::Uniform)
+    } else {
+        None
+    }
+}
+
+/// Provides the passphrase when encrypting. Sources are shared with the
+/// background tasks that encrypt, so they have to be thread safe.
+pub trait PassphraseSource: fmt::Debug + Send + Sync {
+    fn passphrase(&self) -> Result<SecretString, PassphraseError>;
+
+    /// Whether nothing was entered yet, as opposed to something invalid
+    fn is_empty(&self) -> bool;
+}
+
+/// A passphrase typed into the form, optionally typed a second time
+#[derive(Debug, Clone)]
+pub struct Manual {
+    pub passphrase: SecretString,
+    pub confirm: Option<SecretString>,
+}
+
+impl PassphraseSource for Manual {
+    fn passphrase(&self) -> Result<SecretString, PassphraseError> {
+        if self.is_empty() {
+            Err(PassphraseError::Empty)
+        } else if self
+            .confirm
+            .as_ref()
+            .is_some_and(|confirm| confirm.expose_secret() != self.passphrase.expose_secret())
+        {
+            Err(PassphraseError::Mismatch)
+        } else {
+            Ok(self.passphrase.clone())
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.passphrase.expose_secret().is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manual(passphrase: &str, confirm: Option<&str>) -> Manual {
+        Manual {
+            passphrase: passphrase.into(),
+            confirm: confirm.map(SecretString::from),
+        }
+    }
+
+    #[test]
+    fn manual_without_confirmation() {
+        let source = manual("correct horse", None);
+        assert_eq!(
+            source.passphrase().unwrap().expose_secret(),
+            "correct horse"
+        );
+        assert!(!source.is_empty());
+    }
+
+    #[test]
+    fn manual_with_matching_confirmation() {
+        let source = manual("correct horse", Some("correct horse"));
+        assert_eq!(
+            source.passphrase().unwrap().expose_secret(),
+            "correct horse"
+        );
+    }
+
+    #[test]
+    fn manual_with_differing_confirmation() {
+        let source = manual("correct horse", Some("correct house"));
+        assert_eq!(source.passphrase().unwrap_err(), PassphraseError::Mismatch);
+    }
+
+    #[test]
+    fn manual_empty() {
+        let source = manual("", Some(""));
+        assert_eq!(source.passphrase().unwrap_err(), PassphraseError::Empty);
+        assert!(source.is_empty());
+    }
+
+    #[test]
+    fn weak_passphrases() {
+        assert_eq!(weakness("Tr0ub4dor&3"), Some(Weakness::Short));
+        assert_eq!(weakness("abababababababab"), Some(Weakness::Uniform));
+        assert_eq!(weakness("correcthorsebat"), Some(Weakness::Uniform));
+        assert_eq!(weakness("correct horse battery staple"), None);
+        assert_eq!(weakness("Correcthorsebat"), None);
+    }
+
+    #[test]
+    fn manual_as_trait_object() {
+        let source: &dyn PassphraseSource = &manual("correct horse", Some("correct horse"));
+        assert!(source.passphrase().is_ok());
+    }
+}
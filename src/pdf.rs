@@ -0,0 +1,504 @@
+//! PDF generation split into the steps of `paper_age::convenience::create_pdf`,
+//! so that progress can be reported between them.
+
+use age::armor::{ArmoredReader, ArmoredWriter, Format};
+use age::secrecy::SecretString;
+use age::x25519;
+use paper_age::builder::{Document, svg};
+use paper_age::convenience::PaperAgeError;
+use paper_age::encryption::encrypt_plaintext;
+use paper_age::page::PageSize;
+use printpdf::{
+    LineDashPattern, Mm, Op, ParsedFont, PdfFontHandle, PdfPage, Point, Pt, Svg, TextItem, XObject,
+};
+use qrcode::types::QrError;
+use qrcode::{EcLevel, QrCode};
+use sha2::{Digest, Sha256};
+
+use crate::capacity::EccLevel;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::io::{Cursor, Read, Write};
+use std::sync::Arc;
+
+/// Number of steps reported while generating
+pub const STEPS: usize = 3;
+
+/// Title printed when none was entered
+pub const DEFAULT_TITLE: &str = "PaperAge";
+
+/// Label of the notes field when none was entered
+pub const DEFAULT_NOTES_LABEL: &str = "Passphrase:";
+
+const ARMOR_BEGIN: &str = "-----BEGIN AGE ENCRYPTED FILE-----";
+const ARMOR_END: &str = "-----END AGE ENCRYPTED FILE-----";
+
+/// Column width of the armored base64 body
+const ARMOR_COLUMNS: usize = 64;
+
+/// Lines of printed ciphertext that fit between the middle of the page and
+/// the footer at the smallest font size `paper_age` scales them down to
+const MAX_PRINTED_LINES: usize = 44;
+
+/// Start of every binary age file, which tells binary QR codes from text ones
+const AGE_MAGIC: &[u8] = b"age-encryption.org/";
+
+/// Text of the QR code drawn first in the place of a binary one, see
+/// [`insert_binary_qr_code`]
+const QR_PLACEHOLDER: &str = "PaperAge";
+
+/// Font sizes on the instructions page
+const INSTRUCTIONS_HEADING_SIZE: f32 = 14.0;
+const INSTRUCTIONS_TEXT_SIZE: f32 = 11.0;
+
+/// Character width relative to the font size in `paper_age`'s monospace fonts
+const FONT_RATIO: f32 = 3.0 / 5.0;
+
+/// How the sheet is laid out, independent of the secret on it
+#[derive(Debug, Clone)]
+pub struct Layout {
+    pub title: String,
+    pub notes_label: String,
+    pub page_size: PageSize,
+    pub grid: bool,
+    pub min_ecc: EccLevel,
+    /// Print the armored ciphertext below the QR code
+    pub print_armor: bool,
+    /// Keep age's ASCII armor in the QR code instead of the [`compact`] form
+    pub armor: bool,
+    /// Put the raw ciphertext into the QR code instead of text, see
+    /// [`qr_data`]. The payload and the printed text are then [`compact`].
+    pub binary_qr: bool,
+    /// Short identifier such as "Server key", see [`notes_line`]
+    pub qr_caption: String,
+    /// Add the creation date to the notes label, see [`notes_line`]
+    pub date_stamp: bool,
+    /// Font for the title and the notes label instead of `paper_age`'s own
+    pub font: Option<Font>,
+    /// Paragraphs of a page before the sheet explaining how to recover the
+    /// secret, the first one being the heading
+    pub instructions: Option<Vec<String>>,
+    /// Author in the document properties, empty to leave it out
+    pub author: String,
+    /// Subject in the document properties, empty to leave it out
+    pub subject: String,
+}
+
+/// A font file for scripts the default font has no glyphs for, parsed once
+/// when it is loaded
+#[derive(Clone)]
+pub struct Font(Arc<ParsedFont>);
+
+impl Font {
+    /// `None` if `bytes` isn't a font
+    pub fn parse(bytes: &[u8]) -> Option<Font> {
+        ParsedFont::from_bytes(bytes, 0, &mut vec![]).map(|font| Font(Arc::new(font)))
+    }
+
+    /// The first character of `text` the font has no glyph for
+    pub fn missing_glyph(&self, text: &str) -> Option<char> {
+        text.chars()
+            .find(|c| !c.is_whitespace() && self.0.lookup_glyph_index(u32::from(*c)).is_none())
+    }
+}
+
+impl fmt::Debug for Font {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Font").finish_non_exhaustive()
+    }
+}
+
+/// A generated sheet
+#[derive(Debug)]
+pub struct Sheet {
+    pub pdf: Vec<u8>,
+    /// The armored ciphertext in the QR code
+    pub payload: String,
+    /// See [`fingerprint`]
+    pub fingerprint: String,
+}
+
+/// Who can decrypt the secret
+#[derive(Debug, Clone)]
+pub enum Encryption {
+    Passphrase(SecretString),
+    Recipients(Vec<x25519::Recipient>),
+}
+
+/// Encrypt the secret into ASCII-armored age ciphertext
+pub fn encrypt(secret: &[u8], encryption: Encryption) -> Result<String, PaperAgeError> {
+    match encryption {
+        Encryption::Passphrase(passphrase) => {
+            encrypt_plaintext(&mut Cursor::new(secret), passphrase).map(|(_, encrypted)| encrypted)
+        }
+        Encryption::Recipients(recipients) => encrypt_to_recipients(secret, &recipients),
+    }
+    .map_err(|err| PaperAgeError::Encryption(err.to_string()))
+}
+
+/// Same output as `paper_age`'s passphrase encryption, but for public keys
+fn encrypt_to_recipients(
+    secret: &[u8],
+    recipients: &[x25519::Recipient],
+) -> Result<String, Box<dyn std::error::Error>> {
+    let encryptor = age::Encryptor::with_recipients(
+        recipients
+            .iter()
+            .map(|recipient| recipient as &dyn age::Recipient),
+    )?;
+    let mut encrypted = vec![];
+    let armored_writer = ArmoredWriter::wrap_output(&mut encrypted, Format::AsciiArmor)?;
+    let mut writer = encryptor.wrap_output(armored_writer)?;
+    writer.write_all(secret)?;
+    writer.finish().and_then(|armor| armor.finish())?;
+    Ok(String::from_utf8(encrypted)?)
+}
+
+/// The base64 body of an armored payload on a single line, without the
+/// `BEGIN`/`END` lines. Scans of it need [`armor`] before age accepts them.
+pub fn compact(armored: &str) -> String {
+    armored
+        .lines()
+        .filter(|line| *line != ARMOR_BEGIN && *line != ARMOR_END)
+        .collect()
+}
+
+/// Undo [`compact`], leaving armored payloads as they are
+pub fn armor(payload: &str) -> String {
+    if payload.starts_with(ARMOR_BEGIN) {
+        payload.to_string()
+    } else {
+        format!(
+            "{ARMOR_BEGIN}\n{}{ARMOR_END}\n",
+            wrap(payload, ARMOR_COLUMNS)
+        )
+    }
+}
+
+/// Break a compact payload into lines of `columns` characters
+fn wrap(base64: &str, columns: usize) -> String {
+    base64
+        .as_bytes()
+        .chunks(columns)
+        .flat_map(|line| [std::str::from_utf8(line).unwrap_or_default(), "\n"])
+        .collect()
+}
+
+/// Short SHA-256 fingerprint of the armored ciphertext, e.g. `1a2b 3c4d 5e6f 7a8b`.
+///
+/// Encryption is randomized, so it only matches copies of the same generated sheet.
+pub fn fingerprint(encrypted: &str) -> String {
+    let digest = Sha256::digest(encrypted.as_bytes());
+    digest[..8]
+        .chunks(2)
+        .map(|pair| format!("{:02x}{:02x}", pair[0], pair[1]))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Start the page with the title and the QR code, the slowest part of the layout.
+/// Fails if the code would use less error correction than `min_ecc`.
+fn insert_qr_code(
+    title: String,
+    font: Option<Font>,
+    page_size: PageSize,
+    grid: bool,
+    min_ecc: EccLevel,
+    data: Vec<u8>,
+) -> Result<Document, PaperAgeError> {
+    check_ecc(&data, min_ecc)?;
+    let mut document = Document::new(title.clone(), page_size)
+        .map_err(|err| PaperAgeError::DocumentInit(err.to_string()))?;
+    // The title, the notes label and the footer are all drawn with this handle
+    if let Some(Font(font)) = font {
+        document.title_font = PdfFontHandle::External(document.doc.add_font(&font));
+    }
+    if grid {
+        document.draw_grid();
+    }
+    document.insert_title_text(title);
+    // Binary data that happens to be valid UTF-8 gives the same code as text
+    match String::from_utf8(data) {
+        Ok(text) => document
+            .insert_qr_code(text)
+            .map_err(|err| PaperAgeError::PdfCreation(err.to_string()))?,
+        Err(err) => insert_binary_qr_code(&mut document, err.as_bytes())?,
+    }
+    Ok(document)
+}
+
+/// `paper_age` only draws QR codes of text. A small one is drawn instead and
+/// its image swapped for the binary code, stretched to the same nominal size
+/// so it takes the same place on the page.
+fn insert_binary_qr_code(document: &mut Document, data: &[u8]) -> Result<(), PaperAgeError> {
+    let error = |err: &dyn fmt::Display| PaperAgeError::PdfCreation(err.to_string());
+    let xobjects = &document.doc.resources.xobjects.map;
+    let existing: BTreeSet<_> = xobjects.keys().cloned().collect();
+    document
+        .insert_qr_code(QR_PLACEHOLDER.to_string())
+        .map_err(|err| error(&err))?;
+    let placeholder = svg::qrcode(QR_PLACEHOLDER.to_string()).map_err(|err| error(&err))?;
+    let image = qr_svg(data).map_err(|err| error(&err))?;
+    let image = svg_size(&image)
+        .zip(svg_size(&placeholder))
+        .map(
+            |((width, height), (placeholder_width, placeholder_height))| {
+                // The view box keeps the modules of the binary code in proportion
+                image.replacen(
+                    &format!(" width=\"{width}\" height=\"{height}\""),
+                    &format!(" width=\"{placeholder_width}\" height=\"{placeholder_height}\""),
+                    1,
+                )
+            },
+        )
+        .ok_or_else(|| error(&"Failed to parse SVG dimensions"))?;
+    let image = Svg::parse(&image, &mut vec![]).map_err(|err| error(&err))?;
+    let xobjects = &mut document.doc.resources.xobjects.map;
+    let id = xobjects
+        .keys()
+        .find(|id| !existing.contains(*id))
+        .cloned()
+        .ok_or_else(|| error(&"QR code image missing"))?;
+    xobjects.insert(id, XObject::External(image));
+    Ok(())
+}
+
+/// The `width` and `height` attributes of the root element of `svg`
+fn svg_size(svg: &str) -> Option<(&str, &str)> {
+    let attribute = |name: &str| {
+        let start = svg.find(name)? + name.len();
+        let end = svg[start..].find('"')? + start;
+        Some(&svg[start..end])
+    };
+    Some((attribute(" width=\"")?, attribute(" height=\"")?))
+}
+
+/// Same QR code image as `paper_age` draws for text, but of any bytes
+pub fn qr_svg(data: &[u8]) -> Result<String, QrError> {
+    let code = [EcLevel::H, EcLevel::Q, EcLevel::M, EcLevel::L]
+        .into_iter()
+        .find_map(|level| QrCode::with_error_correction_level(data, level).ok())
+        .ok_or(QrError::DataTooLong)?;
+    Ok(code
+        .render()
+        .min_dimensions(256, 256)
+        .dark_color(qrcode::render::svg::Color("#000000"))
+        .light_color(qrcode::render::svg::Color("#ffffff"))
+        .quiet_zone(false)
+        .build())
+}
+
+/// What the QR code for `payload` holds: the payload itself, or with
+/// `binary` the raw age ciphertext, which is a quarter smaller than base64
+pub fn qr_data(payload: &str, binary: bool) -> Result<Vec<u8>, PaperAgeError> {
+    if !binary {
+        return Ok(payload.as_bytes().to_vec());
+    }
+    let mut data = vec![];
+    ArmoredReader::new(armor(payload).as_bytes())
+        .read_to_end(&mut data)
+        .map_err(|err| PaperAgeError::PdfCreation(err.to_string()))?;
+    Ok(data)
+}
+
+/// The [`compact`] payload of a binary QR code, `None` for codes of text
+pub fn from_binary(data: &[u8]) -> Option<String> {
+    if !data.starts_with(AGE_MAGIC) {
+        return None;
+    }
+    let mut armored = vec![];
+    let mut writer = ArmoredWriter::wrap_output(&mut armored, Format::AsciiArmor).ok()?;
+    writer.write_all(data).ok()?;
+    writer.finish().ok()?;
+    Some(compact(std::str::from_utf8(&armored).ok()?))
+}
+
+/// Add a page with recovery instructions. `paper_age` only adds the page of
+/// the sheet when saving, so this one ends up first.
+fn insert_instructions(document: &mut Document, paragraphs: &[String]) {
+    let dimensions = document.page_size.dimensions();
+    let text_width = (dimensions.width - dimensions.margin * 2.0).into_pt().0;
+    let mut ops = vec![
+        Op::StartTextSection,
+        Op::SetTextCursor {
+            pos: Point::new(
+                dimensions.margin,
+                dimensions.height - dimensions.margin - Mm::from(Pt(INSTRUCTIONS_HEADING_SIZE)),
+            ),
+        },
+    ];
+    for (index, paragraph) in paragraphs.iter().enumerate() {
+        let size = if index == 0 {
+            INSTRUCTIONS_HEADING_SIZE
+        } else {
+            INSTRUCTIONS_TEXT_SIZE
+        };
+        ops.push(Op::SetFont {
+            font: document.title_font.clone(),
+            size: Pt(size),
+        });
+        ops.push(Op::SetLineHeight { lh: Pt(size * 1.4) });
+        let columns = (text_width / (FONT_RATIO * size)) as usize;
+        for line in paragraph.lines().flat_map(|line| wrap_words(line, columns)) {
+            ops.push(Op::ShowText {
+                items: vec![TextItem::Text(line)],
+            });
+            ops.push(Op::AddLineBreak);
+        }
+        ops.push(Op::AddLineBreak);
+    }
+    ops.push(Op::EndTextSection);
+    document
+        .doc
+        .pages
+        .push(PdfPage::new(dimensions.width, dimensions.height, ops));
+}
+
+/// Break `text` into lines of at most `columns` characters at spaces. Longer
+/// words, like URLs, get a line of their own.
+fn wrap_words(text: &str, columns: usize) -> Vec<String> {
+    let mut lines = vec![String::new()];
+    for word in text.split(' ') {
+        match lines.last_mut() {
+            Some(line) if line.is_empty() => line.push_str(word),
+            Some(line) if line.chars().count() + 1 + word.chars().count() <= columns => {
+                line.push(' ');
+                line.push_str(word);
+            }
+            _ => lines.push(word.to_string()),
+        }
+    }
+    lines
+}
+
+/// Make sure the QR code for `data` gets at least `min_ecc`
+pub fn check_ecc(data: &[u8], min_ecc: EccLevel) -> Result<(), PaperAgeError> {
+    match EccLevel::chosen_for(data) {
+        Some(level) if level < min_ecc => Err(PaperAgeError::PdfCreation(format!(
+            "error correction level {level:?} is below the minimum {min_ecc:?}"
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Lay out the rest of the page and serialize the PDF. Without `print_armor` the
+/// armored ciphertext is left out and the QR code is the only copy on the page.
+fn finish(
+    mut document: Document,
+    notes_label: String,
+    encrypted: String,
+    print_armor: bool,
+) -> Result<Vec<u8>, PaperAgeError> {
+    document.insert_notes_field(notes_label, false);
+    let dimensions = document.page_size.dimensions();
+    document.draw_line(
+        vec![dimensions.center_left(), dimensions.center_right()],
+        1.0,
+        LineDashPattern {
+            dash_1: Some(5),
+            ..LineDashPattern::default()
+        },
+    );
+    if print_armor {
+        // A compact payload is a single line far wider than the page, armored
+        // ones and parts of split ones already come in lines
+        document.insert_pem_text(if encrypted.contains('\n') {
+            encrypted
+        } else {
+            // Lines as wide as the armor's are easiest to type, but the
+            // largest payloads only fit on the page in wider ones
+            let columns = ARMOR_COLUMNS.max(encrypted.len().div_ceil(MAX_PRINTED_LINES));
+            wrap(&encrypted, columns)
+        });
+    }
+    document.insert_footer();
+    document
+        .save_to_bytes()
+        .map_err(|err| PaperAgeError::PdfCreation(err.to_string()))
+}
+
+/// The line printed before the notes field: the date and the caption if
+/// requested, then the label, e.g. `2026-10-15 | Server key | Passphrase:`.
+///
+/// `paper_age` prints a fixed footer and offers no way to add text elsewhere on
+/// the page, so the notes label is the only free-form line that can hold them.
+pub fn notes_line(notes_label: &str, qr_caption: &str, date_stamp: bool) -> String {
+    let notes_label = if notes_label.is_empty() {
+        DEFAULT_NOTES_LABEL
+    } else {
+        notes_label
+    };
+    let mut parts = vec![];
+    if date_stamp {
+        parts.push(chrono::Local::now().format("%Y-%m-%d").to_string());
+    }
+    if !qr_caption.is_empty() {
+        parts.push(qr_caption.to_string());
+    }
+    parts.push(notes_label.to_string());
+    parts.join(" | ")
+}
+
+/// Encrypt the secret and lay out the whole sheet, calling `on_step` with the
+/// number of finished steps out of [`STEPS`]
+pub fn create(
+    layout: Layout,
+    secret: &[u8],
+    encryption: Encryption,
+    mut on_step: impl FnMut(usize),
+) -> Result<Sheet, PaperAgeError> {
+    let mut encrypted = encrypt(secret, encryption)?;
+    if !layout.armor || layout.binary_qr {
+        encrypted = compact(&encrypted);
+    }
+    on_step(1);
+    lay_out(layout, encrypted, on_step)
+}
+
+/// Lay out a sheet around an already encrypted payload, calling `on_step` like
+/// [`create`] for the remaining steps.
+///
+/// Neither age nor the PDF writer accept fixed randomness, so this is the only
+/// way to get the same QR code twice. The PDF bytes still differ between runs.
+pub fn lay_out(
+    layout: Layout,
+    encrypted: String,
+    mut on_step: impl FnMut(usize),
+) -> Result<Sheet, PaperAgeError> {
+    let title = if layout.title.is_empty() {
+        DEFAULT_TITLE.to_string()
+    } else {
+        layout.title
+    };
+    let notes_label = notes_line(&layout.notes_label, &layout.qr_caption, layout.date_stamp);
+    // A font lacking any of the glyphs would leave gaps, the default one at
+    // least covers Latin text
+    let font = layout.font.filter(|font| {
+        font.missing_glyph(&title)
+            .or_else(|| font.missing_glyph(&notes_label))
+            .is_none()
+    });
+    let mut document = insert_qr_code(
+        title,
+        font,
+        layout.page_size,
+        layout.grid,
+        layout.min_ecc,
+        qr_data(&encrypted, layout.binary_qr)?,
+    )?;
+    if let Some(paragraphs) = &layout.instructions {
+        insert_instructions(&mut document, paragraphs);
+    }
+    // Only fields the user typed for this purpose end up in the properties,
+    // which anyone with the file can read without the passphrase
+    document.doc.metadata.info.author = layout.author;
+    document.doc.metadata.info.subject = layout.subject;
+    on_step(2);
+    let pdf = finish(document, notes_label, encrypted.clone(), layout.print_armor)?;
+    on_step(3);
+    Ok(Sheet {
+        pdf,
+        fingerprint: fingerprint(&encrypted),
+        payload: encrypted,
+    })
+}
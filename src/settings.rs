@@ -0,0 +1,281 @@
+//! User preferences persisted between launches.
+//!
+//! Only non-sensitive form state lives here: the passphrase and the secret
+//! content are never written to disk.
+
+use anyhow::Result;
+use iced::Theme;
+use paper_age::page::PageSize;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::path::PathBuf;
+
+use crate::AppTheme;
+use crate::archive::ArchiveFormat;
+use crate::encoding::TextEncoding;
+use crate::i18n::Language;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Preferences {
+    pub title: String,
+    pub notes_label: String,
+    #[serde(with = "page_size")]
+    pub page_size: PageSize,
+    pub show_heading: bool,
+    /// Replaces the heading above the form, empty for the default one
+    pub heading: String,
+    #[serde(with = "theme")]
+    pub theme: AppTheme,
+    /// Multiplier applied to every text size in the interface
+    pub text_scale: f32,
+    #[serde(with = "language")]
+    pub language: Language,
+    /// Minutes without touching the secret fields before they are cleared
+    pub idle_timeout: u64,
+    /// Folder the file dialogs open in
+    pub last_dir: Option<PathBuf>,
+    /// Strip whitespace around a typed secret before encrypting
+    pub trim_secret: bool,
+    /// Convert CRLF line endings to LF in text secrets before encrypting
+    pub normalize_newlines: bool,
+    /// How several secret files are combined into one
+    pub archive_format: ArchiveFormat,
+    /// Put the creation date next to the notes label
+    pub date_stamp: bool,
+    /// Keep age's ASCII armor in the QR code
+    pub armor: bool,
+    /// Put the raw ciphertext into the QR code instead of text
+    pub binary_qr: bool,
+    /// Put a page with recovery instructions before the sheet
+    pub instructions: bool,
+    /// Read every generated sheet back and decrypt it before showing it
+    pub verify: bool,
+    /// Show a saved PDF in the default viewer right away
+    pub open_after_save: bool,
+    /// Generate the PDF as soon as the clipboard was read into the secret
+    pub clipboard_generate: bool,
+    /// Show a tray icon to quickly encrypt the clipboard, from the next launch
+    /// on. Kept by builds without the `tray` feature too.
+    pub tray: bool,
+    /// Start with the file picker instead of the text editor
+    pub file_secret: bool,
+    /// Author written to the properties of generated PDFs
+    pub author: String,
+    /// Titles of generated sheets, most recent first
+    pub title_history: Vec<String>,
+    /// Notes labels of generated sheets, most recent first
+    pub notes_history: Vec<String>,
+    /// Named sets of form fields, see [`Profile`]
+    pub profiles: Vec<Profile>,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            title: Default::default(),
+            notes_label: Default::default(),
+            page_size: PageSize::A4,
+            show_heading: true,
+            heading: Default::default(),
+            theme: AppTheme::Fixed(Theme::CatppuccinMocha),
+            text_scale: 1.0,
+            language: Language::default(),
+            idle_timeout: 5,
+            last_dir: None,
+            trim_secret: true,
+            normalize_newlines: false,
+            archive_format: Default::default(),
+            date_stamp: false,
+            armor: true,
+            binary_qr: false,
+            instructions: false,
+            verify: true,
+            open_after_save: false,
+            clipboard_generate: true,
+            tray: false,
+            file_secret: false,
+            author: Default::default(),
+            title_history: vec![],
+            notes_history: vec![],
+            profiles: vec![],
+        }
+    }
+}
+
+/// Form fields saved under a name to switch between kinds of backups. Like
+/// the rest of the preferences, this never includes secrets.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    pub name: String,
+    pub title: String,
+    pub notes_label: String,
+    pub qr_caption: String,
+    #[serde(with = "page_size")]
+    pub page_size: PageSize,
+    pub encoding: TextEncoding,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self {
+            name: Default::default(),
+            title: Default::default(),
+            notes_label: Default::default(),
+            qr_caption: Default::default(),
+            page_size: PageSize::A4,
+            encoding: Default::default(),
+        }
+    }
+}
+
+impl Preferences {
+    /// Load preferences, falling back to defaults if they are missing or invalid
+    pub fn load() -> Self {
+        storage::read()
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        storage::write(&toml::to_string(self)?)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod storage {
+    use anyhow::{Context, Result};
+    use directories::ProjectDirs;
+    use std::path::PathBuf;
+
+    fn path() -> Option<PathBuf> {
+        ProjectDirs::from("me", "renesat", "paper-age-gui")
+            .map(|dirs| dirs.config_dir().join("preferences.toml"))
+    }
+
+    pub fn read() -> Option<String> {
+        std::fs::read_to_string(path()?).ok()
+    }
+
+    pub fn write(content: &str) -> Result<()> {
+        let path = path().context("No config directory")?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod storage {
+    use anyhow::{Context, Result, anyhow};
+
+    const KEY: &str = "paper-age-gui.preferences";
+
+    fn local_storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok()?
+    }
+
+    pub fn read() -> Option<String> {
+        local_storage()?.get_item(KEY).ok()?
+    }
+
+    pub fn write(content: &str) -> Result<()> {
+        local_storage()
+            .context("No local storage")?
+            .set_item(KEY, content)
+            .map_err(|err| anyhow!("{err:?}"))
+    }
+}
+
+mod page_size {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &PageSize, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_string())
+    }
+
+    /// Unsupported page sizes fall back to the default instead of discarding
+    /// the whole preferences file
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<PageSize, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Ok(crate::PAGE_SIZES
+            .into_iter()
+            .find(|page_size| page_size.to_string() == name)
+            .unwrap_or(PageSize::A4))
+    }
+}
+
+mod theme {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &AppTheme, serializer: S) -> Result<S::Ok, S::Error> {
+        match value {
+            AppTheme::System => serializer.serialize_str(AppTheme::SYSTEM),
+            AppTheme::Fixed(theme) => serializer.serialize_str(&theme.to_string()),
+        }
+    }
+
+    /// Themes iced renamed or removed fall back to the default
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<AppTheme, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        if name == AppTheme::SYSTEM {
+            return Ok(AppTheme::System);
+        }
+        Ok(Theme::ALL
+            .iter()
+            .find(|theme| theme.to_string() == name)
+            .cloned()
+            .map_or_else(|| Preferences::default().theme, AppTheme::Fixed))
+    }
+}
+
+mod language {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Language, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.id().to_string())
+    }
+
+    /// Languages that are no longer shipped fall back to the default
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Language, D::Error> {
+        let id = String::deserialize(deserializer)?;
+        Ok(Language::ALL
+            .into_iter()
+            .find(|language| language.id().to_string() == id)
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_theme_keeps_other_preferences() {
+        let preferences: Preferences = toml::from_str(
+            r#"
+            title = "Server keys"
+            theme = "No Such Theme"
+
+            [[profiles]]
+            name = "Work"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(preferences.title, "Server keys");
+        assert_eq!(preferences.theme, Preferences::default().theme);
+        assert_eq!(preferences.profiles[0].name, "Work");
+    }
+
+    #[test]
+    fn theme_round_trip() {
+        let preferences = Preferences {
+            theme: AppTheme::Fixed(Theme::Dracula),
+            ..Default::default()
+        };
+        let loaded: Preferences = toml::from_str(&toml::to_string(&preferences).unwrap()).unwrap();
+        assert_eq!(loaded.theme, AppTheme::Fixed(Theme::Dracula));
+    }
+}
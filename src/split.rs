@@ -0,0 +1,170 @@
+//! Spreading a payload too large for one QR code over several sheets.
+//!
+//! The armored ciphertext is cut at line breaks into parts. Every part starts
+//! with a header line like `PaperAge part 2/3 1a2b3c4d` giving its position and
+//! the start of the fingerprint of the whole payload, so the parts can be
+//! scanned in any order and parts of different secrets are never mixed.
+
+use crate::pdf;
+
+const HEADER_PREFIX: &str = "PaperAge part ";
+
+/// Room kept free in every QR code for the header line
+pub const HEADER_SIZE: usize = 32;
+
+/// Most sheets a secret is split across, still a stack that is kept together
+pub const MAX_PARTS: usize = 10;
+
+/// Why a set of parts couldn't be put back together
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JoinError {
+    /// Part numbers that weren't among the scanned ones
+    Missing(Vec<usize>),
+    /// The parts belong to different payloads
+    Mixed,
+}
+
+/// Cut `payload` into parts of at most `capacity` bytes each, headers included.
+///
+/// Armor lines are short enough for any QR code, so a line is never cut.
+pub fn split(payload: &str, capacity: usize) -> Vec<String> {
+    let room = capacity.saturating_sub(HEADER_SIZE);
+    let mut bodies = vec![String::new()];
+    for line in payload.split_inclusive('\n') {
+        match bodies.last_mut() {
+            Some(body) if body.is_empty() || body.len() + line.len() <= room => body.push_str(line),
+            _ => bodies.push(line.to_string()),
+        }
+    }
+    let id = id(payload);
+    let count = bodies.len();
+    bodies
+        .into_iter()
+        .enumerate()
+        .map(|(index, body)| format!("{HEADER_PREFIX}{}/{count} {id}\n{body}", index + 1))
+        .collect()
+}
+
+/// Whether `content` is one part of a split payload
+pub fn is_part(content: &str) -> bool {
+    content.starts_with(HEADER_PREFIX)
+}
+
+/// Put the parts back together in order, whatever order they were scanned in
+pub fn join(parts: &[String]) -> Result<String, JoinError> {
+    let mut headers = vec![];
+    for part in parts {
+        headers.push(header(part).ok_or(JoinError::Mixed)?);
+    }
+    let Some(&(_, count, id, _)) = headers.first() else {
+        return Err(JoinError::Missing(vec![]));
+    };
+    if headers
+        .iter()
+        .any(|&(_, other_count, other_id, _)| other_count != count || other_id != id)
+    {
+        return Err(JoinError::Mixed);
+    }
+    let mut bodies = vec![None; count];
+    for (index, _, _, body) in headers {
+        bodies[index - 1] = Some(body);
+    }
+    let missing: Vec<usize> = (1..=count).filter(|n| bodies[n - 1].is_none()).collect();
+    if !missing.is_empty() {
+        return Err(JoinError::Missing(missing));
+    }
+    let payload: String = bodies.into_iter().flatten().collect();
+    if self::id(&payload) == id {
+        Ok(payload)
+    } else {
+        Err(JoinError::Mixed)
+    }
+}
+
+/// Position, part count, payload id and body of a part. Counts above
+/// [`MAX_PARTS`] were never written by [`split`] and are rejected.
+fn header(part: &str) -> Option<(usize, usize, &str, &str)> {
+    let (header, body) = part.strip_prefix(HEADER_PREFIX)?.split_once('\n')?;
+    let (position, id) = header.split_once(' ')?;
+    let (index, count) = position.split_once('/')?;
+    let (index, count) = (index.parse().ok()?, count.parse().ok()?);
+    (count <= MAX_PARTS && (1..=count).contains(&index)).then_some((index, count, id, body))
+}
+
+/// The first 8 hex digits of the payload fingerprint
+fn id(payload: &str) -> String {
+    pdf::fingerprint(payload).replace(' ', "")[..8].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Lines shaped like an armored payload, different for every `seed`
+    fn payload(seed: usize) -> String {
+        (0..100).map(|line| format!("{seed}{line:063}\n")).collect()
+    }
+
+    #[test]
+    fn split_and_join() {
+        let payload = payload(1);
+        let parts = split(&payload, 1000);
+        assert!(parts.len() > 1);
+        assert!(parts.iter().all(|part| part.len() <= 1000 && is_part(part)));
+        assert_eq!(join(&parts).unwrap(), payload);
+    }
+
+    #[test]
+    fn small_payload_is_one_part() {
+        let payload = payload(1);
+        let parts = split(&payload, 10_000);
+        assert_eq!(parts.len(), 1);
+        assert_eq!(join(&parts).unwrap(), payload);
+    }
+
+    #[test]
+    fn join_out_of_order() {
+        let payload = payload(1);
+        let mut parts = split(&payload, 1000);
+        parts.reverse();
+        parts.swap(0, 1);
+        assert_eq!(join(&parts).unwrap(), payload);
+    }
+
+    #[test]
+    fn join_missing_parts() {
+        let mut parts = split(&payload(1), 1000);
+        let count = parts.len();
+        parts.remove(count - 1);
+        parts.remove(1);
+        assert_eq!(join(&parts), Err(JoinError::Missing(vec![2, count])));
+    }
+
+    #[test]
+    fn join_parts_of_different_payloads() {
+        let mut parts = split(&payload(1), 1000);
+        parts[1] = split(&payload(2), 1000).swap_remove(1);
+        assert_eq!(join(&parts), Err(JoinError::Mixed));
+    }
+
+    #[test]
+    fn join_tampered_part() {
+        let mut parts = split(&payload(1), 1000);
+        parts[0] = parts[0].replacen("0\n", "1\n", 1);
+        assert_eq!(join(&parts), Err(JoinError::Mixed));
+    }
+
+    #[test]
+    fn too_many_parts() {
+        let part = format!("{HEADER_PREFIX}1/{} 1a2b3c4d\nbody\n", usize::MAX);
+        assert_eq!(join(&[part]), Err(JoinError::Mixed));
+        let part = format!("{HEADER_PREFIX}1/{} 1a2b3c4d\nbody\n", MAX_PARTS + 1);
+        assert_eq!(join(&[part]), Err(JoinError::Mixed));
+    }
+
+    #[test]
+    fn not_a_part() {
+        assert!(!is_part("-----BEGIN AGE ENCRYPTED FILE-----\n"));
+        assert_eq!(join(&["hello".to_string()]), Err(JoinError::Mixed));
+    }
+}
@@ -0,0 +1,89 @@
+//! Tray icon with a menu item to quickly encrypt the clipboard.
+//!
+//! Only built with the `tray` feature and only shown once switched on in the
+//! preferences. On Linux the icon is served over D-Bus, so it needs no GTK.
+
+use iced::Subscription;
+use iced::futures::channel::mpsc;
+use iced::futures::{SinkExt, StreamExt};
+use resvg::tiny_skia::{Pixmap, Transform};
+use resvg::usvg::{Options, Tree};
+use tray_icon::menu::{Menu, MenuEvent, MenuItem};
+use tray_icon::{Icon, TrayIcon, TrayIconBuilder};
+
+use crate::Assets;
+use crate::i18n::tr;
+
+/// Edge length of the rendered logo, trays scale it down to their size
+const ICON_SIZE: u32 = 64;
+
+const QUICK_ENCRYPT_ID: &str = "quick-encrypt";
+
+/// Keeps the icon in the tray until it is dropped
+pub struct Tray {
+    _icon: TrayIcon,
+}
+
+impl Tray {
+    /// Show the icon, `None` if the desktop has no tray to put it in. Has to
+    /// run on the main thread once the event loop is running, for macOS.
+    pub fn show() -> Option<Tray> {
+        let menu = Menu::new();
+        menu.append(&MenuItem::with_id(
+            QUICK_ENCRYPT_ID,
+            tr("tray-quick-encrypt"),
+            true,
+            None,
+        ))
+        .ok()?;
+        let icon = TrayIconBuilder::new()
+            .with_menu(Box::new(menu))
+            .with_tooltip(tr("app-title"))
+            .with_icon(logo()?)
+            .build()
+            .ok()?;
+        Some(Tray { _icon: icon })
+    }
+}
+
+/// Fires whenever the quick encrypt item of the menu is chosen
+pub fn quick_encrypt() -> Subscription<()> {
+    Subscription::run(|| {
+        iced::stream::channel(1, async |mut output: mpsc::Sender<()>| {
+            // The tray only offers a blocking receiver, so a thread waits on it
+            // and ends with the first event after the subscription is gone
+            let (sender, mut events) = mpsc::unbounded();
+            std::thread::spawn(move || {
+                while let Ok(event) = MenuEvent::receiver().recv() {
+                    if event.id == QUICK_ENCRYPT_ID && sender.unbounded_send(()).is_err() {
+                        break;
+                    }
+                }
+            });
+            while let Some(()) = events.next().await {
+                let _ = output.send(()).await;
+            }
+        })
+    })
+}
+
+/// The app logo as straight RGBA pixels
+fn logo() -> Option<Icon> {
+    let tree = Tree::from_data(Assets.logo().content(), &Options::default()).ok()?;
+    let scale = ICON_SIZE as f32 / tree.size().width().max(tree.size().height());
+    let mut pixmap = Pixmap::new(ICON_SIZE, ICON_SIZE)?;
+    resvg::render(
+        &tree,
+        Transform::from_scale(scale, scale),
+        &mut pixmap.as_mut(),
+    );
+    let rgba = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let pixel = pixel.demultiply();
+            [pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]
+        })
+        .collect();
+    Icon::from_rgba(rgba, ICON_SIZE, ICON_SIZE).ok()
+}
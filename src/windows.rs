@@ -0,0 +1,183 @@
+//! Independent forms in separate windows.
+//!
+//! Every window owns a complete [`App`], so secrets, passphrases and results
+//! are never shared between them. Only the preferences on disk and the
+//! interface language, see [`crate::i18n`], are common.
+
+use iced::keyboard;
+use iced::{Element, Event, Subscription, Task, Theme, event, window};
+use std::collections::BTreeMap;
+
+use crate::{App, Message};
+
+#[derive(Default)]
+pub struct Windows {
+    apps: BTreeMap<window::Id, App>,
+    #[cfg(feature = "tray")]
+    tray: Option<crate::tray::Tray>,
+}
+
+#[derive(Debug, Clone)]
+pub enum WindowsMessage {
+    App(window::Id, Message),
+    NewWindow,
+    Closed(window::Id),
+    /// Chosen in the tray menu, see [`crate::tray`]
+    #[cfg(feature = "tray")]
+    QuickEncrypt,
+}
+
+impl Windows {
+    pub fn new() -> (Self, Task<WindowsMessage>) {
+        let mut windows = Self::default();
+        #[cfg(feature = "tray")]
+        {
+            windows.tray = crate::settings::Preferences::load()
+                .tray
+                .then(crate::tray::Tray::show)
+                .flatten();
+        }
+        let task = windows.open();
+        (windows, task)
+    }
+
+    /// Open a window with a fresh form
+    fn open(&mut self) -> Task<WindowsMessage> {
+        let (id, opened) = window::open(window::Settings {
+            position: window::Position::Centered,
+            exit_on_close_request: false,
+            ..Default::default()
+        });
+        let (app, task) = App::new();
+        self.apps.insert(id, app);
+        Task::batch([
+            // The layout depends on the width, which is only known once the window exists
+            opened.then(|id| {
+                window::size(id)
+                    .map(move |size| WindowsMessage::App(id, Message::WindowResized(size)))
+            }),
+            task.map(move |message| WindowsMessage::App(id, message)),
+        ])
+    }
+
+    pub fn update(&mut self, message: WindowsMessage) -> Task<WindowsMessage> {
+        match message {
+            WindowsMessage::App(id, Message::LanguageChanged(language)) => {
+                // Every window follows, the one it was picked in goes last so
+                // its preferences are the ones saved
+                let mut ids: Vec<_> = self
+                    .apps
+                    .keys()
+                    .copied()
+                    .filter(|&other| other != id)
+                    .collect();
+                ids.push(id);
+                Task::batch(
+                    ids.into_iter()
+                        .map(|id| self.update_app(id, Message::LanguageChanged(language))),
+                )
+            }
+            WindowsMessage::App(id, message) => self.update_app(id, message),
+            WindowsMessage::NewWindow => self.open(),
+            WindowsMessage::Closed(id) => {
+                self.apps.remove(&id);
+                if self.apps.is_empty() {
+                    iced::exit()
+                } else {
+                    Task::none()
+                }
+            }
+            #[cfg(feature = "tray")]
+            WindowsMessage::QuickEncrypt => {
+                // Window ids count up, so the last one is the newest window
+                let Some(&id) = self.apps.keys().next_back() else {
+                    return Task::none();
+                };
+                Task::batch([
+                    window::minimize(id, false),
+                    window::gain_focus(id),
+                    Task::done(WindowsMessage::App(id, Message::EncryptClipboard)),
+                ])
+            }
+        }
+    }
+
+    fn update_app(&mut self, id: window::Id, message: Message) -> Task<WindowsMessage> {
+        match self.apps.get_mut(&id) {
+            Some(app) => app
+                .update(message)
+                .map(move |message| WindowsMessage::App(id, message)),
+            // The window was closed while the message was in flight
+            None => Task::none(),
+        }
+    }
+
+    pub fn view(&self, id: window::Id) -> Element<'_, WindowsMessage> {
+        match self.apps.get(&id) {
+            Some(app) => app
+                .view()
+                .map(move |message| WindowsMessage::App(id, message)),
+            None => iced::widget::Space::new().into(),
+        }
+    }
+
+    pub fn title(&self, id: window::Id) -> String {
+        self.apps.get(&id).map(App::title).unwrap_or_default()
+    }
+
+    pub fn theme(&self, id: window::Id) -> Option<Theme> {
+        self.apps.get(&id).map(App::theme)
+    }
+
+    /// Ctrl+N, browsers only have room for a single window
+    fn opens_window(key: &keyboard::Key, modifiers: keyboard::Modifiers) -> bool {
+        cfg!(not(target_arch = "wasm32"))
+            && modifiers.command()
+            && !modifiers.shift()
+            && matches!(key.as_ref(), keyboard::Key::Character(c) if c.eq_ignore_ascii_case("n"))
+    }
+
+    pub fn subscription(&self) -> Subscription<WindowsMessage> {
+        // Window events carry the window they happened in, so they are routed
+        // here instead of being subscribed to by every form
+        let window_events = event::listen_with(|event, _, id| {
+            let message = match event {
+                Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) => {
+                    if Windows::opens_window(&key, modifiers) {
+                        return Some(WindowsMessage::NewWindow);
+                    }
+                    App::shortcut(&key, modifiers)
+                }
+                Event::Window(window::Event::FileHovered(_)) => Some(Message::FileHovered(true)),
+                Event::Window(window::Event::FilesHoveredLeft) => Some(Message::FileHovered(false)),
+                Event::Window(window::Event::FileDropped(path)) => Some(Message::FileDropped(path)),
+                Event::Window(window::Event::Resized(size)) => Some(Message::WindowResized(size)),
+                Event::Window(window::Event::CloseRequested) => Some(Message::CloseRequested(id)),
+                _ => None,
+            };
+            message.map(|message| WindowsMessage::App(id, message))
+        });
+        let apps = self.apps.iter().map(|(id, app)| {
+            app.subscription()
+                .with(*id)
+                .map(|(id, message)| WindowsMessage::App(id, message))
+        });
+        #[cfg(feature = "tray")]
+        let tray = if self.tray.is_some() {
+            crate::tray::quick_encrypt().map(|()| WindowsMessage::QuickEncrypt)
+        } else {
+            Subscription::none()
+        };
+        #[cfg(not(feature = "tray"))]
+        let tray = Subscription::none();
+        Subscription::batch(
+            [
+                window_events,
+                window::close_events().map(WindowsMessage::Closed),
+                tray,
+            ]
+            .into_iter()
+            .chain(apps),
+        )
+    }
+}